@@ -0,0 +1,83 @@
+use crate::{grammar::Entry, App, Message};
+use iced::{
+    widget::{button, text, Column},
+    Element, Length,
+};
+use rand::{seq::SliceRandom, thread_rng};
+
+/// Maximum number of answers proposed in multiple-choice mode.
+pub const CHOICES: usize = 4;
+
+/// Indexes of the entries proposed for `content[index]`, the right one included:
+/// distractors are taken in the same grammatical class first, without duplicated answers.
+pub fn candidates(content: &[Entry], index: usize, element: usize) -> Vec<usize> {
+    let mut others = (0..content.len())
+        .filter(|other| *other != index)
+        .collect::<Vec<_>>();
+    others.shuffle(&mut thread_rng());
+    // stable sort: keeps the shuffled order inside each group
+    others.sort_by_key(|other| content[*other].2 != content[index].2);
+
+    let mut result = vec![index];
+    let mut answers = vec![content[index].get(element)];
+    for other in others {
+        if result.len() == CHOICES {
+            break;
+        }
+        let answer = content[other].get(element);
+        if !answer.trim().is_empty() && !answers.contains(&answer) {
+            answers.push(answer);
+            result.push(other);
+        }
+    }
+    result.shuffle(&mut thread_rng());
+    result
+}
+
+impl App {
+    pub fn view_choices(&self) -> Element<'_, Message> {
+        let mut choices = Column::new().spacing(self.spacing).width(Length::Fill);
+        for (nb, index) in self.choices.iter().enumerate() {
+            choices = choices.push(
+                button(
+                    text(format!(
+                        "{}. {}",
+                        nb + 1,
+                        self.content[*index].get(self.element)
+                    ))
+                    .size(self.font_size),
+                )
+                .on_press(Message::Choose(nb))
+                .width(Length::Fill),
+            );
+        }
+        Element::from(choices)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::GramClass;
+
+    #[test]
+    fn candidates_test() {
+        let content = vec![
+            Entry::new("the work", "le travail", GramClass::Noun),
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("the rust", "la rouille", GramClass::Noun),
+            Entry::new("the job", "le travail", GramClass::Noun),
+            Entry::new("to rise", "s'élever", GramClass::Verb),
+            Entry::new("the solution", "la solution", GramClass::Noun),
+            Entry::new("no", "non", GramClass::Adverb),
+        ];
+        for _ in 0..20 {
+            let result = candidates(&content, 0, 1);
+            assert_eq!(result.len(), CHOICES);
+            assert!(result.contains(&0));
+            assert!(!result.contains(&3)); // same answer as the right one
+            assert!(result.contains(&2) && result.contains(&5)); // other nouns first
+        }
+        assert_eq!(candidates(&content[..1], 0, 0), vec![0]);
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{
+    config,
+    filter::{self, Filter},
+    grammar::{check::check, GramClass, Mark},
+    history::History,
+    parse_file, review,
+    settings::{Direction, Sampling},
+    App, Error, Screen, State,
+};
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+const USAGE: &str = "\
+Usage: u-lang [FILE]
+       u-lang quiz FILE [OPTIONS]
+       u-lang --cli FILE [OPTIONS]
+       u-lang check FILE...
+
+Practise FILE in the terminal instead of the window,
+or look for mistakes in JSON vocabulary files.
+
+Options:
+    --direction DIRECTION   second_to_first (default), first_to_second or random
+    --sampling SAMPLING     due (default), random, in_order or missed
+    --in-order              same as --sampling in_order
+    --cards N               ask at most N cards
+    --minutes N             stop the round after N minutes
+    --class CLASS           only ask the entries of this grammatical class (repeatable)
+    --tag TAG               only ask the entries with this tag (repeatable)
+    --lenient               skip the invalid entries
+    --help                  print this help";
+
+/// Options of the terminal mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    file: PathBuf,
+    direction: Option<Direction>,
+    sampling: Option<Sampling>,
+    cards: Option<u16>,
+    minutes: Option<u8>,
+    lenient: bool,
+    filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Quiz(Options),
+    Check(Vec<PathBuf>),
+}
+
+/// Parse the arguments (without the program name): `None` when the window should be opened.
+fn command(args: &[String]) -> Option<Result<Command, String>> {
+    let (command, rest) = args.split_first()?;
+    match command.as_str() {
+        "quiz" | "--cli" => Some(options(rest).map(Command::Quiz)),
+        "check" if rest.is_empty() => Some(Err(String::from("missing FILE"))),
+        "check" => Some(Ok(Command::Check(rest.iter().map(PathBuf::from).collect()))),
+        "--help" | "-h" => Some(Err(String::new())),
+        _ => None,
+    }
+}
+
+fn options(args: &[String]) -> Result<Options, String> {
+    let mut file = None;
+    let mut options = Options {
+        file: PathBuf::new(),
+        direction: None,
+        sampling: None,
+        cards: None,
+        minutes: None,
+        lenient: false,
+        filter: Filter::default(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--direction" => {
+                let value = args.next().map(String::as_str).unwrap_or("");
+                match Direction::ALL
+                    .into_iter()
+                    .find(|direction| value == Into::<&str>::into(*direction))
+                {
+                    Some(direction) => options.direction = Some(direction),
+                    None => return Err(format!("invalid direction \"{}\"", value)),
+                }
+            }
+            "--class" => {
+                let value = args.next().map(String::as_str).unwrap_or("");
+                let class: GramClass = value.into();
+                if class == GramClass::Other && !["other", "Other"].contains(&value) {
+                    return Err(format!("invalid class \"{}\"", value));
+                }
+                filter::toggle(&mut options.filter.classes, class);
+            }
+            "--tag" => match args.next() {
+                Some(tag) => options.filter.tags.push(tag.clone()),
+                None => return Err(String::from("missing TAG")),
+            },
+            "--sampling" => {
+                let value = args.next().map(String::as_str).unwrap_or("");
+                match Sampling::ALL
+                    .into_iter()
+                    .find(|sampling| value == Into::<&str>::into(*sampling))
+                {
+                    Some(sampling) => options.sampling = Some(sampling),
+                    None => return Err(format!("invalid sampling \"{}\"", value)),
+                }
+            }
+            "--in-order" => options.sampling = Some(Sampling::InOrder),
+            "--cards" => {
+                let value = args.next().map(String::as_str).unwrap_or("");
+                match value.parse() {
+                    Ok(cards) => options.cards = Some(cards),
+                    Err(_) => return Err(format!("invalid number of cards \"{}\"", value)),
+                }
+            }
+            "--minutes" => {
+                let value = args.next().map(String::as_str).unwrap_or("");
+                match value.parse() {
+                    Ok(minutes) => options.minutes = Some(minutes),
+                    Err(_) => return Err(format!("invalid number of minutes \"{}\"", value)),
+                }
+            }
+            "--lenient" => options.lenient = true,
+            "--help" | "-h" => return Err(String::new()),
+            other if other.starts_with("--") => {
+                return Err(format!("unknown option \"{}\"", other))
+            }
+            other if file.is_none() => file = Some(PathBuf::from(other)),
+            other => return Err(format!("unexpected argument \"{}\"", other)),
+        }
+    }
+    options.file = file.ok_or(String::from("missing FILE"))?;
+    Ok(options)
+}
+
+/// Run the terminal mode if asked by the arguments, and return its exit code.
+pub fn main(args: &[String]) -> Option<i32> {
+    let command = command(args)?;
+    if let Err(err) = config::load_languages() {
+        eprintln!("warning: {}", err);
+    }
+    let options = match command {
+        Ok(Command::Quiz(options)) => options,
+        Ok(Command::Check(files)) => return Some(check_files(&files)),
+        Err(message) if message.is_empty() => {
+            println!("{}", USAGE);
+            return Some(0);
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return Some(2);
+        }
+    };
+    let mut app = App {
+        history: History::load(),
+        ..App::default()
+    };
+    app.load_config();
+    match load(&mut app, &options) {
+        Ok(()) => (),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return Some(1);
+        }
+    }
+    if let Err(err) = quiz(&mut app, io::stdin().lock(), io::stdout()) {
+        eprintln!("error: {}", err);
+        return Some(1);
+    }
+    // The tasks of the window are never run here, so save what they would
+    let saved = save_reviews(&app)
+        .and_then(|()| async_std::task::block_on(History::save(app.history.to_json())));
+    match saved {
+        Ok(()) => Some(0),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            Some(1)
+        }
+    }
+}
+
+/// Print the problems of each file, failing if there is any.
+fn check_files(files: &[PathBuf]) -> i32 {
+    let mut problems = 0;
+    for file in files {
+        let lints = std::fs::read_to_string(file)
+            .map_err(|err| Error::io(file, err))
+            .and_then(|raw| {
+                check(&raw).map_err(|error| Error::Parse {
+                    path: file.clone(),
+                    error,
+                })
+            });
+        match lints {
+            Ok(lints) => {
+                for lint in &lints {
+                    println!("{}: {}", file.display(), lint);
+                }
+                problems += lints.len();
+            }
+            Err(err) => {
+                println!("{}", err);
+                problems += 1;
+            }
+        }
+    }
+    match problems {
+        0 => 0,
+        _ => {
+            eprintln!("{} problem(s) in {} file(s)", problems, files.len());
+            1
+        }
+    }
+}
+
+fn load(app: &mut App, options: &Options) -> Result<(), Error> {
+    let raw =
+        std::fs::read_to_string(&options.file).map_err(|err| Error::io(&options.file, err))?;
+    let (langs, mut content, warnings) =
+        parse_file(&options.file, &raw, options.lenient, &app.columns).map_err(|error| {
+            Error::Parse {
+                path: options.file.clone(),
+                error,
+            }
+        })?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+    for entry in &mut content {
+        entry.3.source = Some(options.file.clone());
+    }
+    app.files = vec![options.file.clone()];
+    app.schedule = async_std::task::block_on(review::load(vec![review::path(&options.file)]));
+    app.langs = langs;
+    app.direction = options.direction.unwrap_or(app.direction);
+    app.multiple_choice = false;
+    app.filter = options.filter.clone();
+    app.sampling = options.sampling.unwrap_or(app.sampling);
+    app.max_cards = options.cards.unwrap_or(app.max_cards);
+    app.time_limit = options.minutes.unwrap_or(app.time_limit);
+    app.init(content);
+    Ok(())
+}
+
+/// Write the review states of the studied file next to it, like `App::save_reviews` does.
+fn save_reviews(app: &App) -> Result<(), Error> {
+    for file in &app.files {
+        let path = review::path(file);
+        let entries = app
+            .content
+            .iter()
+            .filter(|entry| entry.3.source.as_ref() == Some(file));
+        std::fs::write(&path, app.schedule.subset(entries).to_json())
+            .map_err(|err| Error::io(&path, err))?;
+    }
+    Ok(())
+}
+
+/// Ask every card of the round on `input`, and return the final score.
+fn quiz(app: &mut App, mut input: impl BufRead, mut output: impl Write) -> io::Result<f32> {
+    let total = app.order.len();
+    let mut answered = 0;
+    while let (Screen::Main(State::WaitUserAnswer), Some(nb)) = (&app.screen, app.current) {
+        let asked = app.element;
+        let shown = if app.plural { asked } else { 1 - asked };
+        writeln!(
+            output,
+            "[{}/{}] {}: {}",
+            nb + 1,
+            total,
+            app.langs[shown],
+            app.prompt(nb)
+        )?;
+        write!(output, "{}: ", app.langs[asked])?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            writeln!(output)?;
+            break; // end of input, stop the round here
+        }
+        app.entry = answer.trim().to_string();
+        let _ = app.correct();
+        answered += 1;
+        let right = app.expected(nb).get(asked);
+        let mark = match app.mark {
+            Mark::Exact | Mark::Wrong => String::new(),
+            mark => format!(" ({})", mark),
+        };
+        if app.score.0 == 1.0 {
+            writeln!(output, "  right: {}", right)?;
+        } else {
+            writeln!(output, "  {}{}, expected: {}", app.score.0, mark, right)?;
+        }
+        let _ = app.next();
+    }
+
+    writeln!(
+        output,
+        "Score: {} / {} ({:.2} / 20)",
+        app.score.1,
+        answered,
+        app.score.1 * 20.0 / answered.max(1) as f32
+    )?;
+    Ok(app.score.1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        grammar::{english, french, german, Entry, GramClass},
+        Message,
+    };
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn options_test() {
+        assert_eq!(command(&args("assets/english1.json")), None);
+        assert_eq!(command(&[]), None);
+        assert_eq!(
+            command(&args("quiz list.csv --direction random --in-order")),
+            Some(Ok(Command::Quiz(Options {
+                file: "list.csv".into(),
+                direction: Some(Direction::Random),
+                sampling: Some(Sampling::InOrder),
+                cards: None,
+                minutes: None,
+                lenient: false,
+                filter: Filter::default(),
+            })))
+        );
+        assert_eq!(
+            command(&args(
+                "quiz list.json --class verb --tag chapter-3 --tag weather"
+            )),
+            Some(Ok(Command::Quiz(Options {
+                file: "list.json".into(),
+                direction: None,
+                sampling: None,
+                cards: None,
+                minutes: None,
+                lenient: false,
+                filter: Filter {
+                    classes: vec![GramClass::Verb],
+                    tags: vec!["chapter-3".into(), "weather".into()],
+                },
+            })))
+        );
+        assert!(matches!(
+            command(&args("quiz list.json --class pronoun")),
+            Some(Err(_))
+        ));
+        assert_eq!(
+            command(&args(
+                "quiz list.json --sampling missed --cards 20 --minutes 5"
+            )),
+            Some(Ok(Command::Quiz(Options {
+                file: "list.json".into(),
+                direction: None,
+                sampling: Some(Sampling::Missed),
+                cards: Some(20),
+                minutes: Some(5),
+                lenient: false,
+                filter: Filter::default(),
+            })))
+        );
+        assert!(matches!(
+            command(&args("quiz list.json --cards many")),
+            Some(Err(_))
+        ));
+        assert_eq!(
+            command(&args("check a.json b.json")),
+            Some(Ok(Command::Check(vec!["a.json".into(), "b.json".into()])))
+        );
+        assert!(matches!(command(&args("--cli")), Some(Err(_))));
+        assert!(matches!(command(&args("check")), Some(Err(_))));
+        assert!(matches!(
+            command(&args("--cli a.json --direction up")),
+            Some(Err(_))
+        ));
+    }
+    #[test]
+    fn reviews_test() {
+        let dir = std::env::temp_dir().join(format!("u-lang-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("list.json");
+        let raw = r#"{"lang": ["en", "fr"], "list": [["yes", "oui"], ["no", "non"]]}"#;
+        std::fs::write(&file, raw).unwrap();
+        let options = Options {
+            file: file.clone(),
+            direction: None,
+            sampling: Some(Sampling::InOrder),
+            cards: None,
+            minutes: None,
+            lenient: false,
+            filter: Filter::default(),
+        };
+
+        let mut app = App::default();
+        load(&mut app, &options).unwrap();
+        quiz(
+            &mut app,
+            "yes
+maybe
+"
+            .as_bytes(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(app.history.0.len(), 1); // recorded at the end of the round
+        save_reviews(&app).unwrap();
+        assert!(review::path(&file).exists());
+
+        // Read again by the next round
+        let mut next = App::default();
+        load(&mut next, &options).unwrap();
+        assert_eq!(next.schedule.get(&next.content[0]).streak, 1);
+        assert_eq!(next.schedule.get(&next.content[1]).streak, 0);
+        assert_eq!(next.schedule.get(&next.content[1]).reviews, 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn review_mistakes_test() {
+        let mut app = App {
+            langs: [english::LANG, french::LANG],
+            direction: Direction::SecondToFirst,
+            ..App::default()
+        };
+        app.content = vec![
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("no", "non", GramClass::Adverb),
+            Entry::new("the work", "le travail", GramClass::Noun),
+        ];
+        // Play the round, missing the entries of `wrong`
+        let play = |app: &mut App, wrong: &[usize]| {
+            let answers = app
+                .order
+                .iter()
+                .map(|i| match wrong.contains(i) {
+                    true => "?\n".to_string(),
+                    false => format!("{}\n", app.content[*i].get(0)),
+                })
+                .collect::<String>();
+            quiz(app, answers.as_bytes(), Vec::new()).unwrap();
+        };
+        app.start(vec![0, 1, 2]);
+        play(&mut app, &[1, 2]);
+        assert_eq!(app.mistakes, vec![1, 2]);
+
+        // Only the missed entries, until none is missed
+        let _ = app.update(Message::ReviewMistakes);
+        let mut order = app.order.clone();
+        order.sort();
+        assert_eq!((order, app.round), (vec![1, 2], 2));
+        play(&mut app, &[2]);
+        assert_eq!(app.mistakes, vec![2]);
+        let _ = app.update(Message::ReviewMistakes);
+        assert_eq!((app.order.clone(), app.round), (vec![2], 3));
+        play(&mut app, &[]);
+        assert!(app.mistakes.is_empty());
+        assert_eq!(app.screen, Screen::Main(State::End));
+    }
+    #[test]
+    fn quiz_test() {
+        let mut app = App {
+            langs: [english::LANG, french::LANG],
+            direction: Direction::SecondToFirst,
+            ..App::default()
+        };
+        app.content = vec![
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("the solution", "la solution", GramClass::Noun),
+            Entry::new("to rise", "s'élever", GramClass::Verb),
+        ];
+        app.start(vec![0, 1, 2]);
+        let mut output = Vec::new();
+        let score = quiz(
+            &mut app,
+            "yes\nthe solutoin\nto fall\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(score, 1.5);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("[1/3] Français: oui\nEnglish:   right: yes\n"));
+        assert!(output.contains("  0.5 (typo), expected: the solution\n"));
+        assert!(output.ends_with("Score: 1.5 / 3 (10.00 / 20)\n"));
+
+        // Stopped before the end
+        app.start(vec![0, 1, 2]);
+        let mut output = Vec::new();
+        quiz(&mut app, "yes\n".as_bytes(), &mut output).unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .ends_with("Score: 1 / 1 (20.00 / 20)\n"));
+
+        // Shorter session
+        app.sampling = Sampling::InOrder;
+        app.max_cards = 2;
+        app.init(app.content.clone());
+        assert_eq!(app.order, vec![0, 1]);
+        let mut output = Vec::new();
+        quiz(
+            &mut app,
+            "yes
+the solution
+"
+            .as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("[1/2] Français: oui\n"));
+        assert!(output.ends_with("Score: 2 / 2 (20.00 / 20)\n"));
+
+        // German plural asked after its noun
+        let mut app = App {
+            langs: [german::LANG, french::LANG],
+            direction: Direction::SecondToFirst,
+            ..App::default()
+        };
+        let mut noun = Entry::new(&String::from("die Kraft, -¨e"), "la force", GramClass::Noun);
+        german::split_plurals(&mut noun, &app.langs);
+        app.content = vec![noun];
+        app.start(vec![0]);
+        let mut output = Vec::new();
+        let score = quiz(&mut app, "der Kraft\ndie Kräfte\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(score, 1.);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  0 (wrong gender: die, not der), expected: die Kraft, -¨e\n"));
+        assert!(output.contains("[1/1] Deutsch: die Kraft → plural\n"));
+        assert!(output.contains("  right: die Kräfte\n"));
+    }
+}
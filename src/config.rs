@@ -0,0 +1,178 @@
+use crate::{
+    grammar::{delimited::Columns, rules},
+    settings::{Direction, Sampling},
+    App, Error, Message,
+};
+use iced::{Pixels, Task};
+use json::JsonValue;
+use std::path::PathBuf;
+
+impl App {
+    /// Settings kept between runs, with the last opened file.
+    fn config(&self) -> JsonValue {
+        json::object! {
+            dark_theme: self.dark_theme,
+            font_size: self.font_size.0,
+            spacing: self.spacing,
+            debug_layout: self.debug_layout,
+            direction: Into::<&str>::into(self.direction),
+            typos: self.tolerance.typos,
+            accents: self.tolerance.accents,
+            multiple_choice: self.multiple_choice,
+            plurals: self.plurals,
+            lenient: self.lenient,
+            columns: self.columns.to_string(),
+            max_cards: self.max_cards,
+            sampling: Into::<&str>::into(self.sampling),
+            time_limit: self.time_limit,
+            last_file: self.file().and_then(|path| path.to_str()),
+        }
+    }
+
+    /// Missing or invalid values keep their default.
+    fn apply_config(&mut self, config: &JsonValue) {
+        self.dark_theme = config["dark_theme"].as_bool().unwrap_or(self.dark_theme);
+        self.font_size = config["font_size"].as_f32().map_or(self.font_size, Pixels);
+        self.spacing = config["spacing"].as_f32().unwrap_or(self.spacing);
+        self.debug_layout = config["debug_layout"]
+            .as_bool()
+            .unwrap_or(self.debug_layout);
+        if let Some(direction) = Direction::ALL
+            .into_iter()
+            .find(|direction| config["direction"] == Into::<&str>::into(*direction))
+        {
+            self.direction = direction;
+        }
+        self.tolerance.typos = config["typos"].as_usize().unwrap_or(self.tolerance.typos);
+        self.tolerance.accents = config["accents"]
+            .as_bool()
+            .unwrap_or(self.tolerance.accents);
+        self.multiple_choice = config["multiple_choice"]
+            .as_bool()
+            .unwrap_or(self.multiple_choice);
+        self.plurals = config["plurals"].as_bool().unwrap_or(self.plurals);
+        self.lenient = config["lenient"].as_bool().unwrap_or(self.lenient);
+        self.max_cards = config["max_cards"].as_u16().unwrap_or(self.max_cards);
+        if let Some(sampling) = Sampling::ALL
+            .into_iter()
+            .find(|sampling| config["sampling"] == Into::<&str>::into(*sampling))
+        {
+            self.sampling = sampling;
+        }
+        self.time_limit = config["time_limit"].as_u8().unwrap_or(self.time_limit);
+        if let Some(columns) = config["columns"].as_str().and_then(Columns::parse) {
+            self.columns_spec = columns.to_string();
+            self.columns = columns;
+        }
+    }
+
+    /// Load the settings, and return the last opened file.
+    pub fn load_config(&mut self) -> Option<PathBuf> {
+        let config = json::parse(&read("settings")?).ok()?;
+        self.apply_config(&config);
+        config["last_file"].as_str().map(PathBuf::from)
+    }
+
+    pub fn save_config(&self) -> Task<Message> {
+        Task::perform(
+            write("settings", self.config().pretty(4)),
+            Message::SettingsSaved,
+        )
+    }
+}
+
+/// Register the languages defined in `languages.json`, next to the settings (see `rules::Registry::load`).
+pub fn load_languages() -> Result<(), Error> {
+    match read("languages") {
+        Some(raw) => rules::REGISTRY
+            .load(&raw)
+            .map(|_| ())
+            .map_err(|error| Error::Parse {
+                path: PathBuf::from("languages.json"),
+                error,
+            }),
+        None => Ok(()),
+    }
+}
+
+// Storage of the app's own files: `<name>.json` in the platform config directory,
+// or the `u-lang-<name>` key of the localStorage on the web.
+
+#[cfg(not(target_family = "wasm"))]
+fn path(name: &str) -> Option<PathBuf> {
+    Some(
+        dirs::config_dir()?
+            .join("u-lang")
+            .join(format!("{}.json", name)),
+    )
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn read(name: &str) -> Option<String> {
+    std::fs::read_to_string(path(name)?).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub async fn write(name: &str, raw: String) -> Result<(), Error> {
+    let path = path(name).ok_or(Error::Io {
+        path: None,
+        message: String::from("no configuration directory"),
+    })?;
+    if let Some(dir) = path.parent() {
+        async_std::fs::create_dir_all(dir)
+            .await
+            .map_err(|err| Error::io(dir, err))?;
+    }
+    async_std::fs::write(&path, raw)
+        .await
+        .map_err(|err| Error::io(&path, err))
+}
+
+#[cfg(target_family = "wasm")]
+pub fn read(name: &str) -> Option<String> {
+    web_sys::window()?
+        .local_storage()
+        .ok()??
+        .get_item(&format!("u-lang-{}", name))
+        .ok()?
+}
+
+#[cfg(target_family = "wasm")]
+pub async fn write(name: &str, raw: String) -> Result<(), Error> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok()?)
+        .and_then(|storage| storage.set_item(&format!("u-lang-{}", name), &raw).ok())
+        .ok_or(Error::Io {
+            path: None,
+            message: String::from("localStorage is unavailable"),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::Tolerance;
+
+    #[test]
+    fn config_test() {
+        let app = App {
+            dark_theme: false,
+            font_size: Pixels(20.0),
+            direction: Direction::Random,
+            tolerance: Tolerance {
+                typos: 3,
+                ..Tolerance::default()
+            },
+            sampling: Sampling::Missed,
+            max_cards: 20,
+            ..App::default()
+        };
+        let config = app.config();
+        let mut loaded = App::default();
+        loaded.apply_config(&config);
+        assert_eq!(loaded.config(), config);
+        loaded.apply_config(&json::parse(r#"{"spacing": "wide", "typos": 2}"#).unwrap());
+        assert_eq!(loaded.spacing, app.spacing);
+        assert_eq!(loaded.tolerance.typos, 2);
+    }
+}
@@ -1,14 +1,93 @@
 use crate::{
+    grammar::{Entry, GramClass, Lang},
     style::{self, header_button},
-    App, Message,
+    App, Message, Screen,
 };
 use iced::{
-    widget::{button, column, container, row, scrollable, text, text_input},
-    Alignment, Element, Length,
+    widget::{button, column, container, pick_list, row, scrollable, text, text_input},
+    Element, Length, Task,
 };
 use iced_aw::menu::{self, Item};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    Insert,
+    Append, // at the end of the list, whatever the row
+    Duplicate,
+    Delete,
+    MoveUp,
+    MoveDown,
+}
+
 impl App {
+    pub fn open_editor(&mut self) {
+        self.score = (0.0, 0.0);
+        self.current = None;
+        self.entry = String::new();
+        self.screen = Screen::Editor(
+            self.content
+                .iter()
+                .map(|_| [text_input::Id::unique(), text_input::Id::unique()])
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    /// Apply a row-level action, keeping the text input ids in sync with `self.content`.
+    pub fn edit_entry(&mut self, index: usize, action: Edit) -> Task<Message> {
+        let ids = match &mut self.screen {
+            Screen::Editor(ids) => ids,
+            _ => return Task::none(),
+        };
+        self.current = None;
+        self.entry = String::new();
+        match action {
+            Edit::Insert | Edit::Duplicate => {
+                let entry = match action {
+                    Edit::Duplicate => self.content[index].clone(),
+                    _ => Entry(
+                        Default::default(),
+                        Default::default(),
+                        GramClass::default(),
+                        self.content[index].3.clone(), // from the same file
+                    ),
+                };
+                self.content.insert(index + 1, entry);
+                ids.insert(
+                    index + 1,
+                    [text_input::Id::unique(), text_input::Id::unique()],
+                );
+                if action == Edit::Insert {
+                    return Task::done(Message::EditText(index + 1, 0));
+                }
+            }
+            Edit::Append => {
+                let meta = self.content.last().map(|entry| entry.3.clone());
+                self.content.push(Entry(
+                    Default::default(),
+                    Default::default(),
+                    GramClass::default(),
+                    meta.unwrap_or_default(),
+                ));
+                ids.push([text_input::Id::unique(), text_input::Id::unique()]);
+                return Task::done(Message::EditText(self.content.len() - 1, 0));
+            }
+            Edit::Delete if self.content.len() > 1 => {
+                self.content.remove(index);
+                ids.remove(index);
+            }
+            Edit::MoveUp if index > 0 => {
+                self.content.swap(index, index - 1);
+                ids.swap(index, index - 1);
+            }
+            Edit::MoveDown if index + 1 < self.content.len() => {
+                self.content.swap(index, index + 1);
+                ids.swap(index, index + 1);
+            }
+            _ => (),
+        }
+        Task::none()
+    }
+
     pub fn screen_editor(&self) -> Element<'_, Message> {
         // Header
         let menu_tpl = |items| {
@@ -18,10 +97,22 @@ impl App {
                 .spacing(5.0)
         };
 
+        let new = button(text("New list").size(self.font_size))
+            .on_press(Message::NewList)
+            .style(style::header_button);
+
         let open = button(text("Open").size(self.font_size))
             .on_press(Message::ChooseFile)
             .style(style::header_button);
 
+        let save = button(text("Save").size(self.font_size))
+            .on_press(Message::Save)
+            .style(style::header_button);
+
+        let save_as = button(text("Save as").size(self.font_size))
+            .on_press(Message::SaveAs)
+            .style(style::header_button);
+
         let editor = button(text("Close").size(self.font_size))
             .on_press(Message::EditorClosed(()))
             .style(style::header_button);
@@ -32,7 +123,10 @@ impl App {
                 .style(style::header_button), // see in src/style.rs
             {
                 menu_tpl(iced_aw::menu_items!(
+                    (new)
                     (open)
+                    (save)
+                    (save_as)
                     (editor)
                 )).width(Length::Shrink)
             })
@@ -44,36 +138,165 @@ impl App {
         );
 
         // Main
+        let ids = match &self.screen {
+            Screen::Editor(ids) => ids,
+            _ => panic!(),
+        };
+        let cell = |i: usize, element: usize| {
+            let value = self.content[i].get(element);
+            if self.current == Some(i) && self.element == element {
+                container(
+                    text_input(value.as_str(), &self.entry)
+                        .size(self.font_size)
+                        .id(ids[i][element].clone())
+                        .on_input(Message::TextInputChanged),
+                )
+                .width(Length::FillPortion(3))
+            } else {
+                container(
+                    button(text(value).size(self.font_size))
+                        .on_press(Message::EditText(i, element))
+                        .width(Length::Fill)
+                        .style(header_button),
+                )
+                .width(Length::FillPortion(3))
+            }
+        };
+        let action = |label, message: Option<Message>| {
+            button(text(label).size(self.font_size))
+                .on_press_maybe(message)
+                .style(header_button)
+        };
+        let len = self.content.len();
         let mut main = column![].width(Length::Fill);
         for (i, value) in self.content.iter().enumerate() {
             main = main.push(
                 row![
-                    if self.current == Some(i) {
-                        container(
-                            text_input(value.get(0).as_str(), &value.get(0))
-                                .size(self.font_size)
-                                .id(self.input_id.clone())
-                                .on_input(Message::TextInputChanged),
-                        )
-                        .width(Length::FillPortion(1))
-                    } else {
-                        container(
-                            button(text(format!("{}", value.get(0))).size(self.font_size))
-                                .on_press(Message::EditText(i))
-                                .width(Length::Fill)
-                                .style(header_button),
-                        )
-                        .width(Length::FillPortion(1))
-                    },
-                    text(format!("{}", value.get(1)))
-                        .size(self.font_size)
-                        .align_y(Alignment::Center)
-                        .width(Length::FillPortion(1))
+                    cell(i, 0),
+                    cell(i, 1),
+                    pick_list(GramClass::ALL, Some(value.2.clone()), move |class| {
+                        Message::GramClassSelected(i, class)
+                    })
+                    .text_size(self.font_size)
+                    .width(Length::FillPortion(1)),
+                    action("↑", (i > 0).then_some(Message::EditEntry(i, Edit::MoveUp))),
+                    action(
+                        "↓",
+                        (i + 1 < len).then_some(Message::EditEntry(i, Edit::MoveDown))
+                    ),
+                    action("+", Some(Message::EditEntry(i, Edit::Insert))),
+                    action("⧉", Some(Message::EditEntry(i, Edit::Duplicate))),
+                    action(
+                        "✕",
+                        (len > 1).then_some(Message::EditEntry(i, Edit::Delete))
+                    ),
                 ]
+                .spacing(self.spacing)
                 .padding(self.spacing),
             );
         }
+        // Also when the list is empty
+        main = main.push(
+            container(action(
+                "+ Add entry",
+                Some(Message::EditEntry(len, Edit::Append)),
+            ))
+            .padding(self.spacing),
+        );
+
+        // Languages of the two columns
+        let langs = row![
+            pick_list(Lang::all(), Some(self.langs[0].clone()), |lang| {
+                Message::LangSelected(0, lang)
+            })
+            .text_size(self.font_size)
+            .width(Length::FillPortion(3)),
+            pick_list(Lang::all(), Some(self.langs[1].clone()), |lang| {
+                Message::LangSelected(1, lang)
+            })
+            .text_size(self.font_size)
+            .width(Length::FillPortion(3)),
+            text("").width(Length::FillPortion(1)),
+        ]
+        .spacing(self.spacing)
+        .padding(self.spacing);
+
+        Element::from(column![header, langs, scrollable(main).width(Length::Fill)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        Element::from(column![header, scrollable(main).width(Length::Fill)])
+    #[test]
+    fn edit_entry_test() {
+        let mut app = App {
+            content: vec![
+                Entry::new("yes", "oui", GramClass::Adverb),
+                Entry::new("no", "non", GramClass::Adverb),
+            ],
+            ..App::default()
+        };
+        app.open_editor();
+        let ids = |app: &App| match &app.screen {
+            Screen::Editor(ids) => ids.clone(),
+            _ => panic!("the editor should be open"),
+        };
+        let words = |app: &App| {
+            app.content
+                .iter()
+                .map(|entry| entry.get(0))
+                .collect::<Vec<_>>()
+        };
+        let first = ids(&app);
+
+        let _ = app.edit_entry(0, Edit::Duplicate);
+        assert_eq!(words(&app), vec!["yes", "yes", "no"]);
+        let _ = app.edit_entry(2, Edit::Insert);
+        assert_eq!(words(&app), vec!["yes", "yes", "no", ""]);
+        let _ = app.edit_entry(2, Edit::MoveUp);
+        assert_eq!(words(&app), vec!["yes", "no", "yes", ""]);
+        let _ = app.edit_entry(1, Edit::MoveDown);
+        assert_eq!(words(&app), vec!["yes", "yes", "no", ""]);
+        let _ = app.edit_entry(3, Edit::MoveDown); // already the last one
+        let _ = app.edit_entry(0, Edit::Delete);
+        assert_eq!(words(&app), vec!["yes", "no", ""]);
+
+        // Each text input keeps its id when its entry moves
+        let after = ids(&app);
+        assert_eq!(after.len(), app.content.len());
+        assert_eq!(after[1], first[1]);
+
+        let mut app = App {
+            content: vec![Entry::new("yes", "oui", GramClass::Adverb)],
+            ..App::default()
+        };
+        app.open_editor();
+        let _ = app.edit_entry(0, Edit::Delete); // the last entry stays
+        assert_eq!(app.content.len(), 1);
+
+        let mut app = App {
+            content: Vec::new(),
+            ..App::default()
+        };
+        app.open_editor();
+        let _ = app.update(Message::EditEntry(0, Edit::Append));
+        assert_eq!(app.content, vec![Entry::default()]);
+        assert_eq!(ids(&app).len(), 1);
+        let _ = app.screen_editor();
+    }
+    #[test]
+    fn new_list_test() {
+        let mut app = App {
+            content: vec![Entry::new("yes", "oui", GramClass::Adverb)],
+            files: vec!["list.json".into()],
+            warnings: vec!["warning".into()],
+            ..App::default()
+        };
+        let _ = app.update(Message::NewList);
+        assert_eq!(app.content, vec![Entry::default()]);
+        assert!(app.files.is_empty() && app.warnings.is_empty());
+        assert!(matches!(&app.screen, Screen::Editor(ids) if ids.len() == 1));
     }
 }
@@ -0,0 +1,112 @@
+use crate::{
+    grammar::{Entry, GramClass},
+    App, Message, Theme,
+};
+use iced::widget::{text, toggler};
+use iced_aw::menu::{self, Item};
+
+/// Entries asked in a session: those of one of `classes` and with one of `tags`,
+/// every entry when they are empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub classes: Vec<GramClass>,
+    pub tags: Vec<String>,
+}
+
+impl Filter {
+    pub fn matches(&self, entry: &Entry) -> bool {
+        (self.classes.is_empty() || self.classes.contains(&entry.2))
+            && (self.tags.is_empty() || entry.3.tags.iter().any(|tag| self.tags.contains(tag)))
+    }
+}
+
+/// Add `value` to `list`, or remove it if it is already there.
+pub fn toggle<T: PartialEq>(list: &mut Vec<T>, value: T) {
+    match list.iter().position(|i| *i == value) {
+        Some(index) => {
+            list.remove(index);
+        }
+        None => list.push(value),
+    }
+}
+
+/// Every tag used in `content`, sorted.
+pub fn tags(content: &[Entry]) -> Vec<String> {
+    let mut tags = content
+        .iter()
+        .flat_map(|entry| entry.3.tags.iter().cloned())
+        .collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+impl App {
+    pub fn view_filter(&self) -> menu::Menu<'_, Message, Theme, iced::Renderer> {
+        let switch = |label: String, on: bool, message: Message| {
+            Item::new(
+                toggler(on)
+                    .label(label)
+                    .on_toggle(move |_| message.clone())
+                    .size(self.font_size)
+                    .text_size(self.font_size),
+            )
+        };
+
+        let mut items = vec![Item::new(text("Classes").size(self.font_size))];
+        for class in GramClass::ALL {
+            items.push(switch(
+                class.to_string(),
+                self.filter.classes.contains(&class),
+                Message::ClassFilterToggle(class),
+            ));
+        }
+        let tags = tags(&self.content);
+        if !tags.is_empty() {
+            items.push(Item::new(text("Tags").size(self.font_size)));
+        }
+        for tag in tags {
+            items.push(switch(
+                tag.clone(),
+                self.filter.tags.contains(&tag),
+                Message::TagFilterToggle(tag),
+            ));
+        }
+
+        menu::Menu::new(items)
+            .max_width(11.0 * self.font_size.0)
+            .offset(5.0)
+            .spacing(5.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter_test() {
+        let mut tagged = Entry::new("to rise", "s'élever", GramClass::Verb);
+        tagged.3.tags = vec!["chapter-3".into(), "weather".into()];
+        let content = vec![
+            Entry::new("yes", "oui", GramClass::Adverb),
+            tagged,
+            Entry::new("the work", "le travail", GramClass::Noun),
+        ];
+        let matching = |filter: &Filter| {
+            (0..content.len())
+                .filter(|i| filter.matches(&content[*i]))
+                .collect::<Vec<_>>()
+        };
+        let mut filter = Filter::default();
+        assert_eq!(matching(&filter), vec![0, 1, 2]);
+        toggle(&mut filter.classes, GramClass::Verb);
+        toggle(&mut filter.classes, GramClass::Noun);
+        assert_eq!(matching(&filter), vec![1, 2]);
+        toggle(&mut filter.tags, String::from("weather"));
+        assert_eq!(matching(&filter), vec![1]);
+        toggle(&mut filter.classes, GramClass::Verb);
+        assert!(matching(&filter).is_empty());
+        assert_eq!(tags(&content), vec!["chapter-3", "weather"]);
+    }
+}
@@ -0,0 +1,50 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Unicode-aware version of `eq_ignore_ascii_case`: "Élever" and "élever" are equal.
+pub fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a == b
+        || a.chars()
+            .flat_map(char::to_lowercase)
+            .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Remove the diacritics: "Élever" becomes "Elever", "die Äußerung" becomes "die Ausserung".
+pub fn strip(text: &str) -> String {
+    text.replace('ß', "ss")
+        .replace('ẞ', "SS")
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect()
+}
+
+/// Write umlauts the way German does without them: "die Lösung" becomes "die Loesung".
+pub fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ä' => result.push_str("ae"),
+            'ö' => result.push_str("oe"),
+            'ü' => result.push_str("ue"),
+            'Ä' => result.push_str("Ae"),
+            'Ö' => result.push_str("Oe"),
+            'Ü' => result.push_str("Ue"),
+            _ => result.push(c),
+        }
+    }
+    strip(&result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accents_test() {
+        assert!(eq_ignore_case("Élever", "élever"));
+        assert!(!eq_ignore_case("Losung", "Lösung"));
+        assert_eq!(strip("Élever"), "Elever");
+        assert_eq!(strip("die Äußerung"), "die Ausserung");
+        assert_eq!(transliterate("die Lösung"), "die Loesung");
+        assert_eq!(transliterate("die Äußerung"), "die Aeusserung");
+    }
+}
@@ -0,0 +1,173 @@
+use super::{parse_entry, parse_lenient, rules::split_article, GramClass, GramErr, Lang};
+use json::JsonValue;
+use std::collections::HashMap;
+
+/// Problem found in a list by `check`, in the entry `index` if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "entry {}: {}", index, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Everything `parse` accepts but that is probably a mistake, on top of what it rejects.
+/// Only fails when the file can't be read at all.
+pub fn check(raw: &str) -> Result<Vec<Lint>, GramErr> {
+    parse_lenient(raw)?;
+    let data = json::parse(raw).unwrap_or(JsonValue::Null);
+    let lint = |index, message| Lint { index, message };
+    let mut lints = Vec::new();
+
+    let mut langs = [Lang::default(), Lang::default()];
+    for (element, code) in data["lang"].members().enumerate() {
+        langs[element] = code.as_str().unwrap_or("").into();
+        if matches!(langs[element], Lang::Other(_))
+            && !["Other", "other"].contains(&code.as_str().unwrap_or(""))
+        {
+            lints.push(lint(None, format!("unknown language {}", code.dump())));
+        }
+    }
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (index, raw_entry) in data["list"].members().enumerate() {
+        let mut repaired = Vec::new();
+        let entry = match parse_entry(raw_entry, Some(&mut repaired)) {
+            Ok(entry) => entry,
+            Err((element, found, expected)) => {
+                let error = GramErr::Entry {
+                    index,
+                    element,
+                    found,
+                    expected,
+                };
+                lints.push(lint(None, error.to_string()));
+                continue;
+            }
+        };
+        for (element, found, expected) in repaired {
+            let error = GramErr::Entry {
+                index,
+                element,
+                found,
+                expected,
+            };
+            lints.push(lint(None, error.to_string()));
+        }
+
+        for element in 0..2 {
+            let texts = match &raw_entry[element] {
+                JsonValue::Array(words) => words.iter().filter_map(JsonValue::as_str).collect(),
+                other => other.as_str().into_iter().collect::<Vec<_>>(),
+            };
+            if texts.iter().any(|text| !balanced(text)) {
+                lints.push(lint(
+                    Some(index),
+                    format!("element {} has unbalanced [] brackets", element),
+                ));
+            }
+            let word = entry.word(element);
+            if word
+                .base
+                .iter()
+                .any(|alternative| alternative.trim().is_empty())
+            {
+                lints.push(lint(
+                    Some(index),
+                    format!("element {} has an empty alternative", element),
+                ));
+            }
+            // Articles expected before the nouns by the correction
+            let Some(rules) = langs[element].rules() else {
+                continue;
+            };
+            let articles = rules.articles(&GramClass::Noun);
+            if entry.2 == GramClass::Noun && !articles.is_empty() {
+                for alternative in &word.base {
+                    if !alternative.trim().is_empty()
+                        && split_article(&rules.normalize(alternative), articles).is_none()
+                    {
+                        lints.push(lint(
+                            Some(index),
+                            format!(
+                                "noun \"{}\" has no article ({})",
+                                alternative,
+                                articles.join("/").replace(' ', "")
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        match seen.get(&entry.to_string()) {
+            Some(first) => lints.push(lint(Some(index), format!("duplicate of entry {}", first))),
+            None => {
+                seen.insert(entry.to_string(), index);
+            }
+        }
+    }
+    Ok(lints)
+}
+
+fn balanced(text: &str) -> bool {
+    let mut depth = 0;
+    for c in text.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return false,
+            ']' => depth -= 1,
+            _ => (),
+        }
+    }
+    depth == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn check_test() {
+        let raw = r#"{"lang": ["de", "klingon"], "list": [
+            ["die Arbeit", "le travail", "noun"],
+            ["Kraft", "la force", "noun"],
+            ["die Arbeit", "le travail", "noun"],
+            ["ja [yes", "oui", "adv"],
+            ["nein / ", "non", "adverbe"],
+            ["gehen", "aller", "vrb"],
+            ["der Gast", 3]
+        ]}"#;
+        let lints = check(raw)
+            .unwrap()
+            .iter()
+            .map(Lint::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lints,
+            vec![
+                "unknown language \"klingon\"",
+                "entry 1: noun \"Kraft\" has no article (der/die/das)",
+                "entry 2: duplicate of entry 0",
+                "entry 3: element 0 has unbalanced [] brackets",
+                "entry 4: element 0 has an empty alternative",
+                "entry 5: element 2 is \"vrb\", expected grammatical class",
+                "entry 6: element 1 is a number, expected word",
+            ]
+        );
+        assert!(check("{").is_err());
+        for i in fs::read_dir("assets").unwrap() {
+            let path = i.unwrap().path();
+            let lints = check(&fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(lints, vec![], "{:?}", path);
+        }
+    }
+}
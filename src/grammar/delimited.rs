@@ -0,0 +1,435 @@
+use super::{german, Entry, EntryErr, GramClass, GramErr, Lang, Lenient, Warning, Word};
+use std::path::Path;
+
+/// Role of a column in a delimited file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    First,
+    Second,
+    Class,
+    Tags,
+    FirstDesc,
+    SecondDesc,
+    Skip,
+}
+
+impl From<Column> for &str {
+    fn from(column: Column) -> Self {
+        match column {
+            Column::First => "first",
+            Column::Second => "second",
+            Column::Class => "class",
+            Column::Tags => "tags",
+            Column::FirstDesc => "first_desc",
+            Column::SecondDesc => "second_desc",
+            Column::Skip => "-",
+        }
+    }
+}
+
+/// Mapping of the columns to the elements of the entries, written like `first,second,class`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns(pub Vec<Column>);
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self(vec![Column::First, Column::Second, Column::Class])
+    }
+}
+
+impl Columns {
+    /// `None` unless `first` and `second` are both given, and no column is given twice.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut columns = Vec::new();
+        for name in spec.split(',').map(str::trim) {
+            let column = match name {
+                "first" | "front" | "term" => Column::First,
+                "second" | "back" | "definition" => Column::Second,
+                "class" => Column::Class,
+                "tags" => Column::Tags,
+                "first_desc" => Column::FirstDesc,
+                "second_desc" => Column::SecondDesc,
+                "-" | "" => Column::Skip,
+                _ => return None,
+            };
+            if column != Column::Skip && columns.contains(&column) {
+                return None;
+            }
+            columns.push(column);
+        }
+        (columns.contains(&Column::First) && columns.contains(&Column::Second))
+            .then_some(Self(columns))
+    }
+
+    fn position(&self, column: Column) -> Option<usize> {
+        self.0.iter().position(|other| *other == column)
+    }
+}
+
+impl std::fmt::Display for Columns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.0.iter().map(|column| Into::<&str>::into(*column));
+        write!(f, "{}", names.collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Delimiter of the file according to its extension, `None` for JSON files.
+pub fn delimiter(path: &Path) -> Option<char> {
+    match path.extension()?.to_str()?.to_lowercase().as_str() {
+        "csv" => Some(','),
+        "tsv" | "txt" => Some('\t'),
+        _ => None,
+    }
+}
+
+/// Read a CSV or TSV file, such as exported by Anki or Quizlet.
+/// The `#separator:` and `#columns:` lines of Anki are understood, the latter giving the languages.
+pub fn parse(
+    raw: &str,
+    delimiter: char,
+    columns: &Columns,
+    lenient: bool,
+) -> Result<Lenient, GramErr> {
+    let mut delimiter = delimiter;
+    let mut langs = [Lang::default(), Lang::default()];
+    let mut names = None;
+    let mut header = 0;
+    for line in raw.lines().take_while(|line| line.starts_with('#')) {
+        header += 1;
+        if let Some(separator) = line.strip_prefix("#separator:") {
+            delimiter = match separator.trim() {
+                "Tab" | "tab" => '\t',
+                "Comma" | "comma" => ',',
+                "Semicolon" | "semicolon" => ';',
+                "Space" | "space" => ' ',
+                "Pipe" | "pipe" => '|',
+                "Colon" | "colon" => ':',
+                other => other.chars().next().unwrap_or(delimiter),
+            };
+        } else if let Some(line) = line.strip_prefix("#columns:") {
+            names = Some(line.to_string());
+        }
+    }
+    if let Some(names) = names {
+        let names = names.split(delimiter).collect::<Vec<_>>();
+        for (element, column) in [Column::First, Column::Second].into_iter().enumerate() {
+            if let Some(name) = columns.position(column).and_then(|i| names.get(i)) {
+                langs[element] = name.trim().into();
+            }
+        }
+    }
+
+    let body = raw.split_inclusive('\n').skip(header).collect::<String>();
+    let mut list = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, record) in records(&body, delimiter, header)?.iter().enumerate() {
+        let error = |(element, found, expected)| GramErr::Entry {
+            index,
+            element,
+            found,
+            expected,
+        };
+        let mut repaired = Vec::new();
+        match parse_record(record, columns, lenient.then_some(&mut repaired)) {
+            Ok(entry) => list.push(entry),
+            Err(err) if lenient => warnings.push(Warning {
+                error: error(err),
+                skipped: true,
+            }),
+            Err(err) => return Err(error(err)),
+        }
+        warnings.extend(repaired.into_iter().map(|err| Warning {
+            error: error(err),
+            skipped: false,
+        }));
+    }
+    for entry in &mut list {
+        german::split_plurals(entry, &langs);
+    }
+    Ok((langs, list, warnings))
+}
+
+fn parse_record(
+    record: &[String],
+    columns: &Columns,
+    repaired: Option<&mut Vec<EntryErr>>,
+) -> Result<Entry, EntryErr> {
+    let cell = |column| {
+        columns
+            .position(column)
+            .and_then(|i| record.get(i))
+            .map(|cell| cell.trim().to_string())
+            .filter(|cell| !cell.is_empty())
+    };
+    let mut entry = Entry::default();
+    for (element, (column, desc)) in [
+        (Column::First, Column::FirstDesc),
+        (Column::Second, Column::SecondDesc),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let Some(text) = cell(column) else {
+            return Err((Some(element), "missing".into(), "word"));
+        };
+        let mut word: Word = (&text).into();
+        if let Some(desc) = cell(desc) {
+            word.desc = desc;
+        }
+        entry.set(element, word);
+    }
+    if let Some(gram_class) = cell(Column::Class) {
+        entry.2 = gram_class.as_str().into();
+        if let Some(repaired) = repaired {
+            if entry.2 == GramClass::Other && !["other", "Other"].contains(&gram_class.as_str()) {
+                repaired.push((Some(2), format!("{:?}", gram_class), "grammatical class"));
+            }
+        }
+    }
+    // Separated by spaces, like in Anki
+    if let Some(tags) = cell(Column::Tags) {
+        entry.3.tags = tags.split_whitespace().map(String::from).collect();
+    }
+    Ok(entry)
+}
+
+/// Cells of each non-blank line, with RFC 4180 quotes; `offset` is the number of lines before `raw`.
+fn records(raw: &str, delimiter: char, offset: usize) -> Result<Vec<Vec<String>>, GramErr> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = None; // position of the opening quote
+    let (mut line, mut column) = (offset + 1, 0);
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        column += 1;
+        match (c, quoted) {
+            ('"', Some(_)) if chars.peek() == Some(&'"') => {
+                chars.next();
+                column += 1;
+                field.push('"');
+            }
+            ('"', Some(_)) => quoted = None,
+            ('"', None) if field.trim().is_empty() => quoted = Some((line, column)),
+            ('\n', Some(_)) => {
+                field.push(c);
+                (line, column) = (line + 1, 0);
+            }
+            (_, Some(_)) => field.push(c),
+            ('\r', None) => (),
+            ('\n', None) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                (line, column) = (line + 1, 0);
+            }
+            (c, None) if c == delimiter => record.push(std::mem::take(&mut field)),
+            (c, None) => field.push(c),
+        }
+    }
+    if let Some((line, column)) = quoted {
+        return Err(GramErr::Syntax {
+            line,
+            column,
+            message: String::from("unterminated quote"),
+        });
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|record| record.iter().any(|cell| !cell.trim().is_empty()));
+    Ok(records)
+}
+
+/// Whether `raw` starts with the `#separator:` or `#columns:` lines of Anki, see `parse`.
+pub fn has_header(raw: &str) -> bool {
+    raw.starts_with('#')
+}
+
+/// Write a list with the given columns, readable by `parse`. With `header`, the languages
+/// are written in the lines of Anki; without it, the file is only cards, like for Quizlet.
+pub fn serialize(
+    langs: &[Lang; 2],
+    list: &[Entry],
+    delimiter: char,
+    columns: &Columns,
+    header: bool,
+) -> String {
+    let separator = match delimiter {
+        '\t' => String::from("Tab"),
+        ',' => String::from("Comma"),
+        ';' => String::from("Semicolon"),
+        other => other.to_string(),
+    };
+    let names = columns
+        .0
+        .iter()
+        .map(|column| match column {
+            Column::First => langs[0].to_string(),
+            Column::Second => langs[1].to_string(),
+            other => Into::<&str>::into(*other).to_string(),
+        })
+        .collect::<Vec<_>>();
+    let mut result = match header {
+        true => format!(
+            "#separator:{}\n#html:false\n#columns:{}\n",
+            separator,
+            names.join(&delimiter.to_string())
+        ),
+        false => String::new(),
+    };
+    for entry in list {
+        let cells = columns
+            .0
+            .iter()
+            .map(|column| {
+                let (element, desc) = match column {
+                    Column::First => (0, Column::FirstDesc),
+                    Column::Second => (1, Column::SecondDesc),
+                    Column::FirstDesc => return entry.0.desc.clone(),
+                    Column::SecondDesc => return entry.1.desc.clone(),
+                    Column::Class if entry.2 == GramClass::Other => return String::new(),
+                    Column::Class => return entry.2.to_string(),
+                    Column::Tags => return entry.3.tags.join(" "),
+                    Column::Skip => return String::new(),
+                };
+                match columns.position(desc) {
+                    Some(_) => entry.word(element).to_string(),
+                    None => entry.get(element),
+                }
+            })
+            .map(|cell| quote(cell, delimiter))
+            .collect::<Vec<_>>();
+        result.push_str(&cells.join(&delimiter.to_string()));
+        result.push('\n');
+    }
+    result
+}
+
+fn quote(cell: String, delimiter: char) -> String {
+    if cell.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::{english, french};
+
+    #[test]
+    fn columns_test() {
+        let columns = Columns::parse("term, definition, -, class").unwrap();
+        assert_eq!(columns.to_string(), "first,second,-,class");
+        assert_eq!(
+            Columns::parse(&Columns::default().to_string()),
+            Some(Columns::default())
+        );
+        assert_eq!(Columns::parse("first,class"), None);
+        assert_eq!(Columns::parse("first,second,first"), None);
+        assert_eq!(Columns::parse("first,second,notes"), None);
+        assert_eq!(
+            Columns::parse("first,second,tags").unwrap().to_string(),
+            "first,second,tags"
+        );
+    }
+    #[test]
+    fn parse_test() {
+        let raw = "#separator:tab\n#html:false\n#columns:English\tFrançais\n\
+            the work / the job\tle travail [effort]\tnoun\n\
+            \n\
+            \"to say \"\"yes\"\"\"\t\"dire\n\toui\"\tverb\n";
+        let (langs, list, _) = parse(raw, ',', &Columns::default(), false).unwrap();
+        assert_eq!(langs, [english::LANG, french::LANG]);
+        assert_eq!(
+            list,
+            vec![
+                Entry::new(
+                    Word::new_list(vec!["the work".into(), "the job".into()]),
+                    Word {
+                        desc: "effort".into(),
+                        ..Word::new("le travail")
+                    },
+                    GramClass::Noun
+                ),
+                Entry::new("to say \"yes\"", "dire\n\toui", GramClass::Verb),
+            ]
+        );
+        let columns = Columns::parse("second,first,first_desc").unwrap();
+        let (_, list, _) = parse("oui,yes,answer\n", ',', &columns, false).unwrap();
+        assert_eq!(list[0].0.desc, "answer");
+        assert_eq!(list[0].1, "oui".into());
+
+        assert_eq!(
+            parse("yes,oui\n\"no,non\n", ',', &Columns::default(), false)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: unterminated quote"
+        );
+        let raw = "yes,oui,adv\nno\nwork,travail,nom\n";
+        assert_eq!(
+            parse(raw, ',', &Columns::default(), false)
+                .unwrap_err()
+                .to_string(),
+            "entry 1: element 1 is missing, expected word"
+        );
+        let (_, list, warnings) = parse(raw, ',', &Columns::default(), true).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(warnings.len(), 1);
+    }
+    #[test]
+    fn serialize_test() {
+        let langs = [german::LANG, french::LANG];
+        let list = vec![
+            Entry::new(
+                Word {
+                    desc: "effort".into(),
+                    ..Word::new_list(vec!["die Arbeit".into(), "der Job".into()])
+                },
+                "le travail",
+                GramClass::Noun,
+            ),
+            Entry::new("ja, genau", "oui \"exactement\"", GramClass::Other),
+        ];
+        for (delimiter, spec) in [
+            (',', "first,second,class"),
+            ('\t', "second,first,first_desc,-,class"),
+        ] {
+            let columns = Columns::parse(spec).unwrap();
+            let raw = serialize(&langs, &list, delimiter, &columns, true);
+            assert_eq!(
+                parse(&raw, delimiter, &columns, false).unwrap(),
+                (langs.clone(), list.clone(), vec![])
+            );
+        }
+        assert_eq!(
+            serialize(&langs, &list[1..], ',', &Columns::default(), true),
+            "#separator:Comma\n#html:false\n#columns:Deutsch,Français,class\n\
+            \"ja, genau\",\"oui \"\"exactement\"\"\",\n"
+        );
+
+        let mut tagged = list[1].clone();
+        tagged.3.tags = vec!["chapter-3".into(), "answers".into()];
+        let columns = Columns::parse("first,second,tags").unwrap();
+        let raw = serialize(&langs, &[tagged.clone()], '\t', &columns, true);
+        assert!(raw.ends_with("\tchapter-3 answers\n"));
+        assert_eq!(parse(&raw, '\t', &columns, false).unwrap().1, vec![tagged]);
+
+        // Without the languages of Anki, as exported by Quizlet
+        let raw = "the work,le travail,noun\n\"ja, genau\",oui,\n";
+        let (langs, list, _) = parse(raw, ',', &Columns::default(), false).unwrap();
+        assert!(!has_header(raw));
+        assert_eq!(langs, [Lang::default(), Lang::default()]);
+        assert_eq!(
+            serialize(&langs, &list, ',', &Columns::default(), false),
+            raw
+        );
+        let raw = serialize(&langs, &list, ',', &Columns::default(), true);
+        assert_eq!(
+            parse(&raw, ',', &Columns::default(), false).unwrap().0,
+            langs
+        );
+    }
+}
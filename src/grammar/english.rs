@@ -1,22 +1,23 @@
-use super::{GramClass, Word};
+use super::{rules::LanguageRules, GramClass, Lang};
 
-pub fn correct(word: &Word, answer: &String, gram_class: &GramClass) -> f32 {
-    word.base
-        .iter()
-        .map(|i| match gram_class {
-            _ if i.eq_ignore_ascii_case(answer) => 1.,
-            GramClass::Verb
-                if (&i.chars().collect::<Vec<char>>()[..3]
-                    == "to ".chars().collect::<Vec<char>>()
-                    && answer == &i[3..]) =>
-            {
-                1.
-            }
-            GramClass::Noun if (&i[..4] == "the " && answer == &i[4..]) => 1.,
-            GramClass::Noun if (&i[..2] == "a " && answer == &i[2..]) => 1.,
-            _ => 0.,
-        })
-        .fold(0., |max, val| if val > max { val } else { max })
+pub struct English;
+
+pub const LANG: Lang = Lang::Known(&English);
+
+impl LanguageRules for English {
+    fn name(&self) -> &'static str {
+        "English"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &["english", "en", "en_US", "en_GB"]
+    }
+    fn markers(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Verb => &["to "],
+            GramClass::Noun => &["the ", "a ", "an "],
+            _ => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -25,16 +26,27 @@ mod test {
 
     #[test]
     fn english_verb() {
-        let verb = Entry("to rise".into(), "s'élever".into(), GramClass::Verb);
-        assert_eq!(verb.correct(&"to rise".into(), 0, &Lang::English), 1.);
-        assert_eq!(verb.correct(&"rise".into(), 0, &Lang::English), 1.);
-        assert_eq!(verb.correct(&"rse".into(), 0, &Lang::English), 0.);
+        let verb = Entry::new("to rise", "s'élever", GramClass::Verb);
+        assert_eq!(verb.judge("to rise", 0, &english::LANG).0, 1.);
+        assert_eq!(
+            verb.judge("rise", 0, &english::LANG),
+            (1., Mark::WithoutMarker("to"))
+        );
+        assert_eq!(
+            Mark::WithoutMarker("to").to_string(),
+            "accepted without: to"
+        );
+        assert_eq!(verb.judge("rse", 0, &english::LANG).0, 0.);
     }
     #[test]
     fn english_noun() {
-        let noun = Entry("the solution".into(), "la solution".into(), GramClass::Noun);
-        assert_eq!(noun.correct(&"the solution".into(), 0, &Lang::English), 1.);
-        assert_eq!(noun.correct(&"solution".into(), 0, &Lang::English), 1.);
-        assert_eq!(noun.correct(&"solutio".into(), 0, &Lang::English), 0.);
+        let noun = Entry::new("the solution", "la solution", GramClass::Noun);
+        assert_eq!(noun.judge("the solution", 0, &english::LANG).0, 1.);
+        assert_eq!(noun.judge("solution", 0, &english::LANG).0, 1.);
+        assert_eq!(
+            noun.judge("a solution", 0, &english::LANG),
+            (0., Mark::Wrong)
+        );
+        assert_eq!(noun.judge("solutio", 0, &english::LANG).0, 0.);
     }
 }
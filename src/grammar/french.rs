@@ -1,15 +1,82 @@
-use super::{smart_options, GramClass, Word};
-
-pub fn correct(word: &Word, answer: &String, gram_class: &GramClass) -> f32 {
-    if answer.is_empty() {
-        return 0.;
-    }
-    word.base
-        .iter()
-        .map(|i| match gram_class {
-            _ if i.eq_ignore_ascii_case(answer) => 1.0,
-            GramClass::Noun => smart_options(i, answer, ["le ", "la "].into()),
-            _ => 0.,
-        })
-        .fold(0., |max, val| if val > max { val } else { max })
+use super::{rules::LanguageRules, GramClass, Lang};
+
+pub struct French;
+
+pub const LANG: Lang = Lang::Known(&French);
+
+impl LanguageRules for French {
+    fn name(&self) -> &'static str {
+        "Français"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &[
+            "French",
+            "french",
+            "français",
+            "Francais",
+            "francais",
+            "fr",
+            "fr_FR",
+        ]
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Noun => &[
+                "le ", "la ", "les ", "l'", "un ", "une ", "des ", "du ", "de la ", "de l'", "d'",
+            ],
+            _ => &[],
+        }
+    }
+    // Reflexive verbs, answered without "se" for half of the points
+    fn pronouns(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Verb => &["se ", "s'"],
+            _ => &[],
+        }
+    }
+    // Elided words (l', s', d', qu') with either apostrophe, and with or without a space after it
+    fn normalize(&self, text: &str) -> String {
+        text.replace('’', "'").replace("' ", "'")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grammar::*;
+
+    #[test]
+    fn french_elision() {
+        let correct = |right: &str, answer: &str, gram_class: GramClass| {
+            Entry::new(right, "", gram_class)
+                .judge(answer, 0, &french::LANG)
+                .0
+        };
+        assert_eq!(correct("l'invité", "l’invité", GramClass::Noun), 1.);
+        assert_eq!(correct("l'invité", "L' invité", GramClass::Noun), 1.);
+        assert_eq!(correct("l'arrivée", "arrivée", GramClass::Noun), 0.5);
+        assert_eq!(correct("l'arrivée", "la arrivée", GramClass::Noun), 0.5);
+        assert_eq!(correct("une maison", "les maison", GramClass::Noun), 0.5);
+        assert_eq!(correct("des gens", "des gens", GramClass::Noun), 1.);
+        assert_eq!(correct("de l'eau", "eau", GramClass::Noun), 0.5);
+        assert_eq!(correct("s'élever", "s’élever", GramClass::Verb), 1.);
+        assert_eq!(correct("s'élever", "élever", GramClass::Verb), 0.5);
+        assert_eq!(correct("se laver", "laver", GramClass::Verb), 0.5);
+        assert_eq!(correct("se laver", "s'laver", GramClass::Verb), 0.5);
+        assert_eq!(correct("laver", "se laver", GramClass::Verb), 0.);
+        assert_eq!(correct("jusqu'ici", "jusqu’ ici", GramClass::Adverb), 1.);
+
+        let verb = Entry::new("se laver", "to wash oneself", GramClass::Verb);
+        assert_eq!(
+            verb.judge("laver", 0, &french::LANG),
+            (0.5, Mark::MissingPronoun("se"))
+        );
+        assert_eq!(
+            Mark::MissingPronoun("se").to_string(),
+            "missing reflexive pronoun: se"
+        );
+        assert_eq!(
+            verb.judge("s'laver", 0, &french::LANG),
+            (0.5, Mark::WrongPronoun("se", "s'"))
+        );
+    }
 }
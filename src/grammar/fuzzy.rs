@@ -0,0 +1,145 @@
+use super::{rules::split_article, GramClass, Lang, Word};
+
+fn same(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Costs of the optimal string alignment between `a` and `b` (restricted Damerau-Levenshtein).
+fn matrix(a: &[char], b: &[char]) -> Vec<Vec<usize>> {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if same(a[i - 1], b[j - 1]) { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && same(a[i - 1], b[j - 2]) && same(a[i - 2], b[j - 1]) {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d
+}
+
+/// Number of insertions, deletions, substitutions and transpositions to go from `a` to `b`.
+pub fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    matrix(&a, &b)[a.len()][b.len()]
+}
+
+/// Each char of `answer`, and whether it is right when aligned on `right`.
+pub fn diff(right: &str, answer: &str) -> Vec<(char, bool)> {
+    let a: Vec<char> = right.chars().collect();
+    let b: Vec<char> = answer.chars().collect();
+    let d = matrix(&a, &b);
+    let mut result = Vec::with_capacity(b.len());
+    let (mut i, mut j) = (a.len(), b.len());
+    while j > 0 {
+        if i > 1
+            && j > 1
+            && same(a[i - 1], b[j - 2])
+            && same(a[i - 2], b[j - 1])
+            && d[i][j] == d[i - 2][j - 2] + 1
+            && !same(a[i - 1], b[j - 1])
+        {
+            result.push((b[j - 1], false));
+            result.push((b[j - 2], false));
+            (i, j) = (i - 2, j - 2);
+        } else if i > 0 && d[i][j] == d[i - 1][j - 1] + if same(a[i - 1], b[j - 1]) { 0 } else { 1 }
+        {
+            result.push((b[j - 1], same(a[i - 1], b[j - 1])));
+            (i, j) = (i - 1, j - 1);
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            result.push((b[j - 1], false));
+            j -= 1;
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// Closest alternative of `word` to `answer`, with its distance to `answer`.
+/// The article, pronoun or marker of `lang` an alternative starts with ("the ", "to ", ...) is
+/// never a typo: answers with another one don't match it, answers without one are
+/// compared with the rest of the alternative.
+pub fn closest<'a>(
+    word: &'a Word,
+    answer: &str,
+    lang: &Lang,
+    gram_class: &GramClass,
+) -> Option<(usize, &'a str)> {
+    let articles = lang
+        .rules()
+        .map(|rules| {
+            [
+                rules.articles(gram_class),
+                rules.pronouns(gram_class),
+                rules.markers(gram_class),
+            ]
+            .concat()
+        })
+        .unwrap_or_default();
+    let given = split_article(answer, &articles);
+    word.base
+        .iter()
+        .filter_map(|right| match (split_article(right, &articles), given) {
+            (Some((article, _)), Some((other, _))) => (article == other).then_some(right.as_str()),
+            (Some((_, rest)), None) => Some(rest),
+            (None, _) => Some(right.as_str()),
+        })
+        .filter(|candidate| !candidate.trim().is_empty())
+        .map(|candidate| (distance(candidate, answer), candidate))
+        .min_by_key(|(distance, _)| *distance)
+}
+
+/// Typos allowed for `right`: none for short words, so that "no" isn't "na".
+pub fn allowed(right: &str, max: usize) -> usize {
+    max.min(right.chars().count() / 4)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::english;
+
+    #[test]
+    fn distance_test() {
+        assert_eq!(distance("solution", "solutio"), 1);
+        assert_eq!(distance("Lösung", "lösugn"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+    }
+    #[test]
+    fn diff_test() {
+        let marks = |right, answer| {
+            diff(right, answer)
+                .into_iter()
+                .map(|(_, ok)| if ok { '.' } else { 'x' })
+                .collect::<String>()
+        };
+        assert_eq!(marks("solution", "solutoin"), ".....xx.");
+        assert_eq!(marks("solution", "solytion"), "...x....");
+        assert_eq!(marks("solution", "sollution"), "..x......");
+        assert_eq!(marks("solution", "slution"), ".......");
+    }
+    #[test]
+    fn closest_test() {
+        let word = Word::new_list(vec!["the solution".into(), "the answer".into()]);
+        let closest = |answer| closest(&word, answer, &english::LANG, &GramClass::Noun);
+        assert_eq!(closest("solutio"), Some((1, "solution")));
+        assert_eq!(closest("the answr"), Some((1, "the answer")));
+        assert_eq!(closest("a answr"), None);
+        assert_eq!(closest("teh answer"), Some((4, "answer")));
+        assert_eq!(closest("answer"), Some((0, "answer")));
+        assert_eq!(closest("lution"), Some((2, "solution")));
+    }
+}
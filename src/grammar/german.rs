@@ -1,18 +1,91 @@
-use super::{smart_options, GramClass, Word};
+use super::{
+    rules::{split_article, LanguageRules},
+    Entry, GramClass, Lang, Word,
+};
 
-pub fn correct(word: &Word, answer: &String, gram_class: &GramClass) -> f32 {
-    if answer.is_empty() {
-        return 0.;
+pub const ARTICLES: [&str; 3] = ["der ", "die ", "das "];
+
+pub struct German;
+
+pub const LANG: Lang = Lang::Known(&German);
+
+impl LanguageRules for German {
+    fn name(&self) -> &'static str {
+        "Deutsch"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &["German", "german", "deutsch", "de", "de_DE"]
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Noun => &ARTICLES,
+            GramClass::Verb => &["jdn ", "jdm "],
+            _ => &[],
+        }
+    }
+    // The article is what gives the gender of the nouns
+    fn gender(&self, gram_class: &GramClass) -> bool {
+        *gram_class == GramClass::Noun
+    }
+}
+
+/// Read the plurals written after the German nouns of `entry`, like `die Kraft, -¨e`:
+/// in another language, or for another class, ", -" is simply part of the text.
+pub fn split_plurals(entry: &mut Entry, langs: &[Lang; 2]) {
+    if entry.2 != GramClass::Noun {
+        return;
+    }
+    for (element, lang) in langs.iter().enumerate() {
+        if *lang == LANG {
+            let mut word = entry.word(element).clone();
+            word.split_plurals();
+            entry.set(element, word);
+        }
     }
+}
+
+/// Plural forms of the options of `word` that give one.
+pub fn plurals(word: &Word) -> Vec<String> {
     word.base
         .iter()
-        .map(|i| match gram_class {
-            _ if i.eq_ignore_ascii_case(answer) => 1.,
-            GramClass::Noun => smart_options(i, answer, ["der ", "die ", "das "].into()),
-            GramClass::Verb => smart_options(i, answer, ["jdn ", "jdm "].into()),
-            _ => 0.0,
-        })
-        .fold(0., |max, val| if val > max { val } else { max })
+        .zip(&word.plural)
+        .filter_map(|(base, plural)| Some(plural_form(base, plural.as_deref()?)))
+        .collect()
+}
+
+/// Apply a plural written like in dictionaries: `die Kraft, -¨e` gives `die Kräfte`.
+pub fn plural_form(singular: &str, plural: &str) -> String {
+    let (article, noun) = match split_article(singular, &ARTICLES) {
+        Some((_, noun)) => ("die ", noun),
+        None => ("", singular),
+    };
+    let mut noun = noun.chars().collect::<Vec<_>>();
+    if plural.contains('¨') {
+        // On the last a, o or u, or on the a of au
+        if let Some(mut i) = noun.iter().rposition(|c| "aouAOU".contains(*c)) {
+            if i > 0 && "uU".contains(noun[i]) && "aA".contains(noun[i - 1]) {
+                i -= 1;
+            }
+            noun[i] = match noun[i] {
+                'a' => 'ä',
+                'o' => 'ö',
+                'u' => 'ü',
+                'A' => 'Ä',
+                'O' => 'Ö',
+                _ => 'Ü',
+            };
+        }
+    }
+    let ending = plural
+        .chars()
+        .filter(|c| !['-', '¨'].contains(c))
+        .collect::<String>();
+    format!(
+        "{}{}{}",
+        article,
+        noun.into_iter().collect::<String>(),
+        ending.trim()
+    )
 }
 
 #[cfg(test)]
@@ -21,9 +94,63 @@ mod test {
 
     #[test]
     fn german_noun() {
-        let noun = Entry("die Kraft".into(), "la force".into(), GramClass::Noun);
-        assert_eq!(noun.correct(&"die Kraft".into(), 0, &Lang::German), 1.);
-        assert_eq!(noun.correct(&"das Kraft".into(), 0, &Lang::German), 0.5);
-        assert_eq!(noun.correct(&"Kraft".into(), 0, &Lang::German), 0.5);
+        let noun = Entry::new("die Kraft", "la force", GramClass::Noun);
+        assert_eq!(noun.judge("die Kraft", 0, &german::LANG).0, 1.);
+        assert_eq!(noun.judge("das Kraft", 0, &german::LANG).0, 0.);
+        assert_eq!(noun.judge("Kraft", 0, &german::LANG).0, 0.5);
+        assert_eq!(
+            noun.correct_with("der Kraft", 0, &german::LANG, &Tolerance::default()),
+            (0., Mark::Gender("die", "der"))
+        );
+        assert_eq!(
+            noun.correct_with("die Kraf", 0, &german::LANG, &Tolerance::default()),
+            (0.5, Mark::Typo)
+        );
+        assert_eq!(
+            noun.correct_with("dir Kraft", 0, &german::LANG, &Tolerance::default())
+                .0,
+            0.
+        );
+        assert_eq!(
+            noun.correct_with("das Kraf", 0, &german::LANG, &Tolerance::default())
+                .0,
+            0.
+        );
+        assert_eq!(
+            Mark::Gender("die", "der").to_string(),
+            "wrong gender: die, not der"
+        );
+    }
+    #[test]
+    fn german_plural() {
+        let mut word: Word = (&String::from("die Kraft, -¨e / das Haus, ¨-er")).into();
+        word.split_plurals();
+        assert_eq!(word.base, vec!["die Kraft", "das Haus"]);
+        assert_eq!(word.to_string(), "die Kraft, -¨e / das Haus, ¨-er");
+        assert_eq!(german::plurals(&word), vec!["die Kräfte", "die Häuser"]);
+        assert_eq!(german::plural_form("das Wunder", "-"), "die Wunder");
+        assert_eq!(german::plural_form("der Fremde", "-n"), "die Fremden");
+        assert_eq!(german::plural_form("der Mangel", "-¨"), "die Mängel");
+        assert_eq!(german::plural_form("Story", "-s"), "Storys");
+
+        let raw = r#"{"lang": ["Deutsch", "English"], "list": [
+            ["die Kraft, -¨e", "the strength, -ish", "noun"],
+            ["ja, -ja", "yes, -ish", "adverb"]
+        ]}"#;
+        let (_, list) = parse(&raw.to_string()).unwrap();
+        assert_eq!(list[0].0.base, vec!["die Kraft"]);
+        assert_eq!(list[0].0.plural, vec![Some("-¨e".to_string())]);
+        assert_eq!(list[0].1.base, vec!["the strength, -ish"]);
+        assert_eq!(list[1].0.base, vec!["ja, -ja"]);
+        assert_eq!(list[1].1.base, vec!["yes, -ish"]);
+
+        let noun = Entry::new(word, "la force", GramClass::Noun);
+        let tolerance = Tolerance::default();
+        let correct = |answer: &str| noun.correct_plural(answer, 0, &tolerance);
+        assert_eq!(correct("die Kräfte"), (1., Mark::Exact));
+        assert_eq!(correct("die Häuser"), (1., Mark::Alternative(1)));
+        assert_eq!(correct("Kräfte"), (0.5, Mark::MissingArticle("die")));
+        assert_eq!(correct("der Kräfte"), (0., Mark::Gender("die", "der")));
+        assert_eq!(correct("die Kraft"), (0., Mark::Wrong));
     }
 }
@@ -0,0 +1,63 @@
+use super::{rules::LanguageRules, GramClass, Lang};
+
+pub struct Italian;
+
+pub const LANG: Lang = Lang::Known(&Italian);
+
+impl LanguageRules for Italian {
+    fn name(&self) -> &'static str {
+        "Italiano"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &["Italian", "italian", "italiano", "it", "it_IT"]
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Noun => &["il ", "lo ", "la ", "l'", "i ", "gli ", "le "],
+            _ => &[],
+        }
+    }
+    // Reflexive verbs: "si lavare" for "lavarsi", "lavare" only gets half of the points
+    fn markers(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Verb => &["si "],
+            _ => &[],
+        }
+    }
+    fn endings(&self, gram_class: &GramClass) -> &[(&'static str, &'static str)] {
+        match gram_class {
+            GramClass::Verb => &[("rsi", "re")],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grammar::*;
+
+    #[test]
+    fn italian_noun() {
+        let noun = Entry::new("la forza", "la force", GramClass::Noun);
+        assert_eq!(noun.judge("la forza", 0, &italian::LANG).0, 1.);
+        assert_eq!(noun.judge("il forza", 0, &italian::LANG).0, 0.5);
+        assert_eq!(noun.judge("forza", 0, &italian::LANG).0, 0.5);
+        let noun = Entry::new("l'amico", "l'ami", GramClass::Noun);
+        assert_eq!(noun.judge("amico", 0, &italian::LANG).0, 0.5);
+        assert_eq!(noun.judge("lo amico", 0, &italian::LANG).0, 0.5);
+        assert_eq!(noun.judge("l'amica", 0, &italian::LANG).0, 0.);
+    }
+    #[test]
+    fn italian_verb() {
+        let verb = Entry::new("lavarsi", "se laver", GramClass::Verb);
+        assert_eq!(verb.judge("lavarsi", 0, &italian::LANG).0, 1.);
+        assert_eq!(
+            verb.judge("lavare", 0, &italian::LANG),
+            (0.5, Mark::MissingEnding("rsi"))
+        );
+        assert_eq!(verb.judge("si lavare", 0, &italian::LANG).0, 1.);
+        assert_eq!(verb.judge("lavar", 0, &italian::LANG).0, 0.);
+        let verb = Entry::new("divertire", "amuser", GramClass::Verb);
+        assert_eq!(verb.judge("divertirsi", 0, &italian::LANG).0, 0.);
+    }
+}
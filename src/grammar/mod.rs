@@ -1,41 +1,203 @@
 use json::JsonValue;
+use std::path::PathBuf;
 
 pub mod word;
 pub use word::*;
+pub mod accents;
+pub mod check;
+pub mod delimited;
 pub mod english;
 pub mod french;
+pub mod fuzzy;
 pub mod german;
+pub mod italian;
+pub mod portuguese;
+pub mod rules;
+pub mod spanish;
+use rules::LanguageRules;
+
+/// Score given to an answer accepted as a typo.
+pub const TYPO_SCORE: f32 = 0.5;
+/// Factor applied to the score of an answer only right without its diacritics.
+pub const ACCENTS_SCORE: f32 = 0.75;
+
+/// Leniency of the correction on top of the language rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tolerance {
+    pub typos: usize,  // maximum edit distance, see `fuzzy::allowed`
+    pub accents: bool, // accept missing diacritics, "Losung" or "Loesung" for "Lösung"
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            typos: 1,
+            accents: true,
+        }
+    }
+}
+
+/// How an answer was accepted, or why it only got a part of the points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Exact,              // exactly the first option
+    Alternative(usize), // exactly another option, by its index
+    MissingArticle(&'static str),
+    MissingEnding(&'static str), // of a reflexive verb, see `LanguageRules::endings`
+    MissingPronoun(&'static str), // of a reflexive verb, see `LanguageRules::pronouns`
+    WithoutMarker(&'static str), // left out, see `LanguageRules::markers`
+    Proclitic(&'static str),     // before the verb instead of its ending, like "se lavar"
+    WrongArticle(&'static str, &'static str), // right and given articles
+    WrongPronoun(&'static str, &'static str), // right and given reflexive pronouns
+    Typo,
+    Accents,
+    Gender(&'static str, &'static str), // right and given articles, see `LanguageRules::gender`
+    #[default]
+    Wrong,
+}
+
+impl std::fmt::Display for Mark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mark::Exact => write!(f, "exact answer"),
+            Mark::Alternative(index) => write!(f, "matched alternative #{}", index + 1),
+            Mark::MissingArticle(article) => write!(f, "missing article: {}", article),
+            Mark::MissingEnding(ending) => write!(f, "missing reflexive ending: {}", ending),
+            Mark::MissingPronoun(pronoun) => write!(f, "missing reflexive pronoun: {}", pronoun),
+            Mark::WithoutMarker(marker) => write!(f, "accepted without: {}", marker),
+            Mark::Proclitic(pronoun) => write!(f, "accepted with the pronoun before: {}", pronoun),
+            Mark::WrongArticle(right, found) => {
+                write!(f, "wrong article: {}, not {}", right, found)
+            }
+            Mark::WrongPronoun(right, found) => {
+                write!(f, "wrong reflexive pronoun: {}, not {}", right, found)
+            }
+            Mark::Typo => write!(f, "typo"),
+            Mark::Accents => write!(f, "missing accents"),
+            Mark::Gender(right, found) => write!(f, "wrong gender: {}, not {}", right, found),
+            Mark::Wrong => write!(f, "wrong answer"),
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
-pub struct Entry(pub Word, pub Word, pub GramClass);
+pub struct Entry(pub Word, pub Word, pub GramClass, pub Meta);
+
+/// What is known of an entry besides its content.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Meta {
+    pub source: Option<PathBuf>, // file it was read from
+    pub tags: Vec<String>,
+    pub flipped: bool, // elements swapped from the order of its file, see `Entry::key`
+}
 
 impl Entry {
-    pub fn get(&self, element: usize) -> String {
-        let word = match element {
+    pub fn new(first: impl Into<Word>, second: impl Into<Word>, gram_class: GramClass) -> Self {
+        Self(first.into(), second.into(), gram_class, Meta::default())
+    }
+    pub fn word(&self, element: usize) -> &Word {
+        match element {
             0 => &self.0,
             1 => &self.1,
             _ => panic!("Unavailable index"),
-        };
+        }
+    }
+    pub fn get(&self, element: usize) -> String {
+        let word = self.word(element);
         if word.desc.is_empty() {
             format!("{}", word)
         } else {
             format!("{} [{}]", word, &word.desc)
         }
     }
-    pub fn correct(&self, answer: &String, element: usize, lang: &Lang) -> f32 {
-        let word = match element {
-            0 => &self.0,
-            1 => &self.1,
+    pub fn set(&mut self, element: usize, word: Word) {
+        match element {
+            0 => self.0 = word,
+            1 => self.1 = word,
             _ => panic!("Unavailable index"),
-        };
-        match *lang {
-            Lang::Other if word.base.contains(answer) => 1.,
-            Lang::English => english::correct(word, answer, &self.2),
-            Lang::French => french::correct(word, answer, &self.2),
-            Lang::German => german::correct(word, answer, &self.2),
-            _ => 0.,
         }
     }
+    /// Name of the entry in the order of its file, which stays the same when the languages
+    /// of the file are swapped in a session.
+    pub fn key(&self) -> String {
+        match self.3.flipped {
+            true => format!("{} = {}", &self.1, &self.0),
+            false => self.to_string(),
+        }
+    }
+    /// Score of `answer` for the element, with the reason of it.
+    pub fn judge(&self, answer: &str, element: usize, lang: &Lang) -> (f32, Mark) {
+        judge_word(self.word(element), answer, &self.2, lang)
+    }
+    /// Same as `judge`, but gives partial credit to answers without their diacritics
+    /// or close enough to be typos.
+    pub fn correct_with(
+        &self,
+        answer: &str,
+        element: usize,
+        lang: &Lang,
+        tolerance: &Tolerance,
+    ) -> (f32, Mark) {
+        let (score, mark) = self.judge(answer, element, lang);
+        if score == 1. || answer.is_empty() || matches!(mark, Mark::Gender(..)) {
+            return (score, mark);
+        }
+        let word = self.word(element);
+        if tolerance.accents {
+            let folded = [accents::strip, accents::transliterate]
+                .iter()
+                .map(|fold| {
+                    let word = Word {
+                        base: word.base.iter().map(|i| fold(i)).collect(),
+                        ..word.clone()
+                    };
+                    judge_word(&word, &fold(answer), &self.2, lang).0 * ACCENTS_SCORE
+                })
+                .fold(0., f32::max);
+            if folded > score {
+                return (folded, Mark::Accents);
+            }
+        }
+        if score >= TYPO_SCORE {
+            return (score, mark);
+        }
+        match fuzzy::closest(word, answer, lang, &self.2) {
+            Some((distance, right)) if distance <= fuzzy::allowed(right, tolerance.typos) => {
+                (TYPO_SCORE, Mark::Typo)
+            }
+            _ => (score, mark),
+        }
+    }
+    /// Same as `correct_with` for the German plural of the element, see `german::plurals`.
+    pub fn correct_plural(
+        &self,
+        answer: &str,
+        element: usize,
+        tolerance: &Tolerance,
+    ) -> (f32, Mark) {
+        let plural = Word::new_list(german::plurals(self.word(element)));
+        Entry(plural, Word::default(), self.2.clone(), Meta::default()).correct_with(
+            answer,
+            0,
+            &german::LANG,
+            tolerance,
+        )
+    }
+}
+
+fn judge_word(word: &Word, answer: &str, gram_class: &GramClass, lang: &Lang) -> (f32, Mark) {
+    match lang.rules() {
+        Some(rules) => rules.judge(word, answer, gram_class),
+        None => match word
+            .base
+            .iter()
+            .position(|right| accents::eq_ignore_case(right, answer))
+        {
+            Some(0) => (1., Mark::Exact),
+            Some(index) => (1., Mark::Alternative(index)),
+            None => (0., Mark::Wrong),
+        },
+    }
 }
 
 impl std::fmt::Display for Entry {
@@ -44,134 +206,370 @@ impl std::fmt::Display for Entry {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub enum Lang {
-    English,
-    French,
-    German,
-    Other,
+    Known(&'static dyn LanguageRules), // see `rules::Registry`
+    Other(String),                     // as written in the file, to save it back
 }
 
-impl<'a> Into<&'a str> for Lang {
-    fn into(self) -> &'a str {
+impl Default for Lang {
+    fn default() -> Self {
+        Lang::Other(String::new())
+    }
+}
+
+impl Lang {
+    /// Every language, those loaded at runtime included.
+    pub fn all() -> Vec<Lang> {
+        let mut all = rules::REGISTRY.all();
+        all.push(Lang::default());
+        all
+    }
+
+    /// How the answers are corrected, `None` for an exact match only.
+    pub fn rules(&self) -> Option<&'static dyn LanguageRules> {
         match self {
-            Self::English => "English",
-            Self::German => "Deutsch",
-            Self::French => "Français",
-            Self::Other => "Other",
+            Lang::Known(rules) => Some(*rules),
+            Lang::Other(_) => None,
+        }
+    }
+}
+
+// Languages are the same when they have the same name
+impl PartialEq for Lang {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Lang::Known(a), Lang::Known(b)) => a.name() == b.name(),
+            (Lang::Other(a), Lang::Other(b)) => a == b,
+            _ => false,
         }
     }
 }
 
+impl Eq for Lang {}
+
+impl std::fmt::Debug for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
 impl From<&str> for Lang {
     fn from(value: &str) -> Self {
-        match value {
-            "English" | "english" | "en" | "en_US" | "en_GB" => Self::English,
-            "German" | "Deutsch" | "german" | "deutsch" | "de" | "de_DE" => Self::German,
-            "French" | "Français" | "french" | "français" | "Francais" | "francais" | "fr"
-            | "fr_FR" => Self::French,
-            _ => Self::Other,
-        }
+        rules::REGISTRY.find(value)
     }
 }
 
 impl std::fmt::Display for Lang {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let string = match self {
-            Lang::English => "English",
-            Lang::German => "Deutsch",
-            Lang::French => "Français",
-            Lang::Other => "Other",
-        };
-        write!(f, "{}", string)
+        match self {
+            Lang::Known(rules) => write!(f, "{}", rules.name()),
+            Lang::Other(code) if code.is_empty() => write!(f, "Other"),
+            Lang::Other(code) => write!(f, "{}", code),
+        }
     }
 }
 
 pub fn parse(raw: &String) -> Result<([Lang; 2], Vec<Entry>), GramErr> {
-    match json::parse(raw.as_str()) {
-        Ok(data) if data["lang"].len() == 2 && data["list"].is_array() => {
-            let lang1: Lang = data["lang"][0].as_str().unwrap_or("").into();
-            let lang2: Lang = data["lang"][1].as_str().unwrap_or("").into();
-
-            let mut list = Vec::new();
-            if let JsonValue::Array(unparsed_list) = &data["list"] {
-                for unparsed_entry in unparsed_list {
-                    match parse_entry(unparsed_entry) {
-                        Ok(entry) => list.push(entry),
-                        Err(_) => return Err(GramErr::LangErr),
-                    }
-                }
-            }
-            Ok(([lang1, lang2], list))
+    parse_with(raw, false).map(|(langs, list, _)| (langs, list))
+}
+
+/// Languages, entries and warnings of a list read by `parse_lenient`.
+pub type Lenient = ([Lang; 2], Vec<Entry>, Vec<Warning>);
+
+/// Same as `parse`, but skips the invalid entries and repairs what it can,
+/// with a warning for each of them.
+pub fn parse_lenient(raw: &str) -> Result<Lenient, GramErr> {
+    parse_with(raw, true)
+}
+
+fn parse_with(raw: &str, lenient: bool) -> Result<Lenient, GramErr> {
+    let data = json::parse(raw).map_err(|err| syntax_error(raw, err))?;
+    if data["lang"].len() != 2 {
+        return Err(GramErr::Field {
+            name: "lang",
+            found: kind(&data["lang"]),
+            expected: "a pair of languages",
+        });
+    }
+    let lang1: Lang = data["lang"][0].as_str().unwrap_or("").into();
+    let lang2: Lang = data["lang"][1].as_str().unwrap_or("").into();
+
+    let JsonValue::Array(unparsed_list) = &data["list"] else {
+        return Err(GramErr::Field {
+            name: "list",
+            found: kind(&data["list"]),
+            expected: "an array of entries",
+        });
+    };
+    let mut list = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, unparsed_entry) in unparsed_list.iter().enumerate() {
+        let error = |(element, found, expected)| GramErr::Entry {
+            index,
+            element,
+            found,
+            expected,
+        };
+        let mut repaired = Vec::new();
+        match parse_entry(unparsed_entry, lenient.then_some(&mut repaired)) {
+            Ok(entry) => list.push(entry),
+            Err(err) if lenient => warnings.push(Warning {
+                error: error(err),
+                skipped: true,
+            }),
+            Err(err) => return Err(error(err)),
         }
-        Err(_) => Err(GramErr::Unknown),
-        _ => Err(GramErr::LangErr),
+        warnings.extend(repaired.into_iter().map(|err| Warning {
+            error: error(err),
+            skipped: false,
+        }));
     }
+    let langs = [lang1, lang2];
+    for entry in &mut list {
+        german::split_plurals(entry, &langs);
+    }
+    Ok((langs, list, warnings))
 }
 
-fn parse_entry(raw: &JsonValue) -> Result<Entry, GramErr> {
-    let mut entry = Entry::default();
-    match parse_word(&raw[0]) {
-        Ok(word) => entry.0 = word,
-        Err(_) => return Err(GramErr::JsonErr),
+/// On failure, the element at fault (if any), what was found there and what was expected.
+type EntryErr = (Option<usize>, String, &'static str);
+
+/// Problems worked around are added to `repaired` when there is one (lenient mode).
+fn parse_entry(
+    raw: &JsonValue,
+    mut repaired: Option<&mut Vec<EntryErr>>,
+) -> Result<Entry, EntryErr> {
+    if !raw.is_array() {
+        return Err((None, kind(raw), "an array"));
     }
-    match parse_word(&raw[1]) {
-        Ok(word) => entry.1 = word,
-        Err(_) => return Err(GramErr::JsonErr),
+    let mut entry = Entry::default();
+    for element in 0..2 {
+        let err = (Some(element), kind(&raw[element]), "word");
+        match (parse_word(&raw[element]), &mut repaired) {
+            (Some(word), _) => entry.set(element, word),
+            (None, Some(repaired)) => {
+                // Keep the valid alternatives
+                let words = raw[element]
+                    .members()
+                    .filter_map(|word| Some(word.as_str()?.to_string()))
+                    .collect::<Vec<_>>();
+                if words.is_empty() {
+                    return Err(err);
+                }
+                entry.set(element, Word::new_list(words));
+                repaired.push(err);
+            }
+            (None, None) => return Err(err),
+        }
     }
     match &raw[2] {
         JsonValue::Null => entry.2 = GramClass::default(),
-        JsonValue::String(gram_class) => entry.2 = gram_class.into(),
-        JsonValue::Short(gram_class) => entry.2 = gram_class.as_str().into(),
-        _ => return Err(GramErr::JsonErr),
+        JsonValue::String(_) | JsonValue::Short(_) => {
+            let gram_class = raw[2].as_str().unwrap_or("");
+            entry.2 = gram_class.into();
+            if let Some(repaired) = &mut repaired {
+                if entry.2 == GramClass::Other && !["", "other", "Other"].contains(&gram_class) {
+                    repaired.push((Some(2), format!("{:?}", gram_class), "grammatical class"));
+                }
+            }
+        }
+        other => return Err((Some(2), kind(other), "grammatical class")),
+    }
+    match &raw[3] {
+        JsonValue::Null => (),
+        JsonValue::String(_) | JsonValue::Short(_) => {
+            entry.3.tags = vec![raw[3].as_str().unwrap_or("").to_string()]
+        }
+        JsonValue::Array(tags) => {
+            entry.3.tags = tags
+                .iter()
+                .filter_map(|tag| Some(tag.as_str()?.to_string()))
+                .collect();
+            if entry.3.tags.len() != tags.len() {
+                match repaired {
+                    Some(repaired) => repaired.push((Some(3), kind(&raw[3]), "tags")),
+                    None => return Err((Some(3), kind(&raw[3]), "tags")),
+                }
+            }
+        }
+        other => return Err((Some(3), kind(other), "tags")),
     }
     Ok(entry)
 }
 
-fn parse_word(raw: &JsonValue) -> Result<Word, GramErr> {
+fn parse_word(raw: &JsonValue) -> Option<Word> {
     match &raw {
-        JsonValue::String(word) => Ok(word.into()),
-        JsonValue::Short(word) => Ok(word.into()),
+        JsonValue::String(word) => Some(word.into()),
+        JsonValue::Short(word) => Some(word.into()),
         JsonValue::Array(unparsed_words) => {
             let mut words = Vec::new();
             for unparsed_word in unparsed_words {
-                match unparsed_word {
-                    JsonValue::String(word) => words.push(word.as_str()),
-                    JsonValue::Short(word) => words.push(word.as_str()),
-                    _ => return Err(GramErr::JsonErr),
-                    // _ => words.push(""),
-                }
+                words.push(unparsed_word.as_str()?.to_string());
             }
-            let words: Vec<String> = words.iter().map(|word| (*word).to_string()).collect();
-            Ok(Word::new_list(words))
+            Some(Word::new_list(words))
+        }
+        _ => None,
+    }
+}
+
+/// Kind of a JSON value, as written in the error messages.
+fn kind(raw: &JsonValue) -> String {
+    match raw {
+        JsonValue::Null => "missing".into(),
+        JsonValue::Short(_) | JsonValue::String(_) => "a string".into(),
+        JsonValue::Number(_) => "a number".into(),
+        JsonValue::Boolean(_) => "a boolean".into(),
+        JsonValue::Object(_) => "an object".into(),
+        JsonValue::Array(array) => match array.iter().find(|value| !value.is_string()) {
+            Some(value) if array.len() <= 3 => format!("an array with {}", kind(value)),
+            _ => format!("an array of {} elements", array.len()),
+        },
+    }
+}
+
+fn syntax_error(raw: &str, err: json::Error) -> GramErr {
+    let end = || {
+        let line = raw.lines().count().max(1);
+        (
+            line,
+            raw.lines().last().map_or(0, |last| last.chars().count()) + 1,
+        )
+    };
+    let (line, column, message) = match err {
+        json::Error::UnexpectedCharacter { ch, line, column } => {
+            (line, column, format!("unexpected character '{}'", ch))
+        }
+        json::Error::UnexpectedEndOfJson => {
+            let (line, column) = end();
+            (line, column, "unexpected end of file".into())
+        }
+        err => (1, 1, err.to_string()),
+    };
+    GramErr::Syntax {
+        line,
+        column,
+        message,
+    }
+}
+
+/// Write a list back in the format read by `parse`, one entry per line.
+pub fn serialize(langs: &[Lang; 2], list: &[Entry]) -> String {
+    let langs = langs
+        .iter()
+        .map(|lang| json::stringify(lang.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let list = list
+        .iter()
+        .map(|entry| format!("\t\t{}", serialize_entry(entry)))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!(
+        "{{\n\t\"lang\": [{}],\n\t\"list\": [\n{}\n\t]\n}}\n",
+        langs, list
+    )
+}
+
+fn serialize_entry(entry: &Entry) -> String {
+    let mut elements = vec![serialize_word(&entry.0), serialize_word(&entry.1)];
+    if !entry.3.tags.is_empty() {
+        elements.push(match entry.2 {
+            GramClass::Other => JsonValue::Null,
+            _ => JsonValue::from(entry.2.to_string()),
+        });
+        elements.push(JsonValue::from(entry.3.tags.clone()));
+    } else if entry.2 != GramClass::Other {
+        elements.push(JsonValue::from(entry.2.to_string()));
+    }
+    format!(
+        "[{}]",
+        elements
+            .into_iter()
+            .map(json::stringify)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn serialize_word(word: &Word) -> JsonValue {
+    // The description can only be read back from the string form
+    if !word.desc.is_empty() {
+        JsonValue::from(format!("{} [{}]", word, word.desc))
+    } else if word.base.len() == 1 {
+        JsonValue::from(word.options().remove(0))
+    } else {
+        JsonValue::from(word.options())
+    }
+}
+
+/// Problem found by `parse_lenient`: the entry was either skipped or read as well as possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub error: GramErr,
+    pub skipped: bool,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.skipped {
+            true => write!(f, "{} (skipped)", self.error),
+            false => write!(f, "{} (repaired)", self.error),
         }
-        _ => return Err(GramErr::JsonErr),
     }
 }
 
+/// Why a list could not be read; entries and elements are counted from 0.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GramErr {
-    JsonErr,
-    LangErr,
-    Unknown,
-}
-
-pub fn smart_options(right: &String, answer: &str, options: Vec<&str>) -> f32 {
-    let size = options[0].len();
-    if options.contains(&&right[..size]) {
-        if (&right[size..]).eq_ignore_ascii_case(answer) {
-            0.5
-        } else if answer.len() >= size + 1 {
-            if (&right[size..]).eq_ignore_ascii_case(&answer[size..]) {
-                0.5
-            } else {
-                0.0
-            }
-        } else {
-            0.0
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Field {
+        name: &'static str,
+        found: String,
+        expected: &'static str,
+    },
+    Entry {
+        index: usize,
+        element: Option<usize>, // `None` when the entry itself is wrong
+        found: String,
+        expected: &'static str,
+    },
+}
+
+impl std::fmt::Display for GramErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GramErr::Syntax {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            GramErr::Field {
+                name,
+                found,
+                expected,
+            } => write!(f, "\"{}\" is {}, expected {}", name, found, expected),
+            GramErr::Entry {
+                index,
+                element: Some(element),
+                found,
+                expected,
+            } => write!(
+                f,
+                "entry {}: element {} is {}, expected {}",
+                index, element, found, expected
+            ),
+            GramErr::Entry {
+                index,
+                element: None,
+                found,
+                expected,
+            } => write!(f, "entry {} is {}, expected {}", index, found, expected),
         }
-    } else {
-        0.0
     }
 }
 
@@ -182,17 +580,142 @@ mod test {
 
     #[test]
     fn entry_test() {
-        let entry = Entry(
+        let entry = Entry::new(
             Word::new("the solution"),
             Word::new("la solution"),
             GramClass::Noun,
         );
         assert_eq!(
-            entry.correct(&String::from("the solution"), 0, &Lang::English),
+            entry
+                .judge(&String::from("the solution"), 0, &english::LANG)
+                .0,
             1.
         );
     }
     #[test]
+    fn typo_test() {
+        let noun = Entry::new("the solution", "la solution", GramClass::Noun);
+        let tolerance = Tolerance::default();
+        let correct =
+            |answer: &str, tolerance| noun.correct_with(answer, 0, &english::LANG, &tolerance);
+        assert_eq!(
+            correct("solution", tolerance),
+            (1., Mark::WithoutMarker("the"))
+        );
+        assert_eq!(correct("solutio", tolerance), (TYPO_SCORE, Mark::Typo));
+        assert_eq!(correct("the soltion", tolerance), (TYPO_SCORE, Mark::Typo));
+        assert_eq!(
+            correct(
+                "soluton",
+                Tolerance {
+                    typos: 0,
+                    ..tolerance
+                }
+            ),
+            (0., Mark::Wrong)
+        );
+        assert_eq!(correct("sultion", tolerance), (0., Mark::Wrong));
+        assert_eq!(
+            correct(
+                "sultion",
+                Tolerance {
+                    typos: 2,
+                    ..tolerance
+                }
+            ),
+            (TYPO_SCORE, Mark::Typo)
+        );
+        // Only the articles and the markers are left out, not any word before a space
+        for (right, answer) in [("raise a question", "question"), ("fashion show", "show")] {
+            let entry = Entry::new(right, "", GramClass::Noun);
+            assert_eq!(
+                entry.correct_with(answer, 0, &english::LANG, &tolerance),
+                (0., Mark::Wrong)
+            );
+        }
+        let entry = Entry::new("", "défilé de mode", GramClass::Noun);
+        assert_eq!(
+            entry.correct_with("mode", 1, &french::LANG, &tolerance),
+            (0., Mark::Wrong)
+        );
+        let short = Entry::new("no", "non", GramClass::Adverb);
+        assert_eq!(
+            short.correct_with("na", 0, &english::LANG, &tolerance),
+            (0., Mark::Wrong)
+        );
+    }
+    #[test]
+    fn other_lang_test() {
+        let entry = Entry::new("Oui", "ÉLEVER", GramClass::Other);
+        let other = Lang::from("klingon");
+        let tolerance = Tolerance::default();
+        assert_eq!(entry.judge("oui", 0, &other), (1., Mark::Exact));
+        assert_eq!(entry.judge("élever", 1, &other), (1., Mark::Exact));
+        assert_eq!(
+            entry.correct_with("OUI", 0, &other, &tolerance),
+            (1., Mark::Exact)
+        );
+        assert_eq!(entry.judge("non", 0, &other), (0., Mark::Wrong));
+    }
+    #[test]
+    fn verdict_test() {
+        let noun = Entry::new(
+            Word::new_list(vec!["the house".into(), "the home".into()]),
+            &String::from("la maison / le foyer"),
+            GramClass::Noun,
+        );
+        let judge = |answer: &str, element, lang| noun.judge(answer, element, &lang);
+        assert_eq!(judge("the house", 0, english::LANG), (1., Mark::Exact));
+        assert_eq!(
+            judge("the home", 0, english::LANG),
+            (1., Mark::Alternative(1))
+        );
+        assert_eq!(
+            judge("foyer", 1, french::LANG),
+            (0.5, Mark::MissingArticle("le"))
+        );
+        assert_eq!(
+            judge("le maison", 1, french::LANG),
+            (0.5, Mark::WrongArticle("la", "le"))
+        );
+        assert_eq!(judge("la cuisine", 1, french::LANG), (0., Mark::Wrong));
+        assert_eq!(
+            Mark::MissingArticle("le").to_string(),
+            "missing article: le"
+        );
+        assert_eq!(Mark::Alternative(1).to_string(), "matched alternative #2");
+    }
+    #[test]
+    fn accents_test() {
+        let tolerance = Tolerance::default();
+        let verb = Entry::new("to rise", "Élever", GramClass::Verb);
+        assert_eq!(verb.judge("élever", 1, &french::LANG).0, 1.);
+        let noun = Entry::new("die Lösung", "la solution", GramClass::Noun);
+        let correct =
+            |answer: &str, tolerance| noun.correct_with(answer, 0, &german::LANG, &tolerance);
+        assert_eq!(
+            correct("die Losung", tolerance),
+            (ACCENTS_SCORE, Mark::Accents)
+        );
+        assert_eq!(
+            correct("die loesung", tolerance),
+            (ACCENTS_SCORE, Mark::Accents)
+        );
+        assert_eq!(
+            correct("Losung", tolerance),
+            (0.5 * ACCENTS_SCORE, Mark::Accents)
+        );
+        let strict = Tolerance {
+            typos: 0,
+            accents: false,
+        };
+        assert_eq!(correct("die Losung", strict), (0., Mark::Wrong));
+        let noun = Entry::new("die Äußerung", "la déclaration", GramClass::Noun);
+        for answer in ["die Aeusserung", "die Ausserung", "die äußerung"] {
+            assert!(noun.correct_with(answer, 0, &german::LANG, &tolerance).0 >= 0.75);
+        }
+    }
+    #[test]
     fn parse_test() {
         let raw = String::from(
             r#"{
@@ -210,15 +733,15 @@ mod test {
         println!("{}", raw);
         let parsed = parse(&raw).unwrap();
         let truth = (
-            [Lang::English, Lang::French],
+            [english::LANG, french::LANG],
             vec![
-                Entry("yes".into(), "oui".into(), GramClass::Adverb),
-                Entry("no".into(), "non".into(), GramClass::Adverb),
-                Entry("the work".into(), "le travail".into(), GramClass::Noun),
-                Entry("the rust".into(), "la rouille".into(), GramClass::Noun),
-                Entry("the solution".into(), "la solution".into(), GramClass::Noun),
-                Entry(
-                    "to rise".into(),
+                Entry::new("yes", "oui", GramClass::Adverb),
+                Entry::new("no", "non", GramClass::Adverb),
+                Entry::new("the work", "le travail", GramClass::Noun),
+                Entry::new("the rust", "la rouille", GramClass::Noun),
+                Entry::new("the solution", "la solution", GramClass::Noun),
+                Entry::new(
+                    "to rise",
                     Word::new_list(vec!["s'élever".into(), "monter".into()]),
                     GramClass::Verb,
                 ),
@@ -227,6 +750,125 @@ mod test {
         assert_eq!(parsed, truth);
     }
     #[test]
+    fn serialize_test() {
+        let langs = [german::LANG, french::LANG];
+        let list = vec![
+            Entry::new("die Kraft", "la force", GramClass::Noun),
+            Entry::new(
+                "das Seil",
+                Word::new_list(vec!["la corde".into(), "la laisse".into()]),
+                GramClass::Noun,
+            ),
+            Entry::new(
+                &String::from("die Bank / die Sitzbank [zum Sitzen]"),
+                &String::from("le banc [\"assis\"]"),
+                GramClass::Other,
+            ),
+            Entry(
+                "gehen".into(),
+                "aller".into(),
+                GramClass::Other,
+                Meta {
+                    tags: vec!["chapter-3".into(), "verbs".into()],
+                    ..Meta::default()
+                },
+            ),
+        ];
+        let raw = serialize(&langs, &list);
+        assert!(raw.contains(r#"["gehen", "aller", null, ["chapter-3","verbs"]]"#));
+        assert_eq!(parse(&raw).unwrap(), (langs, list));
+        // Unknown languages are kept as written
+        let raw = serialize(&["nl".into(), french::LANG], &[]);
+        assert!(raw.contains(r#""lang": ["nl", "Français"]"#));
+        assert_eq!(parse(&raw).unwrap().0, ["nl".into(), french::LANG]);
+        for i in fs::read_dir("assets").unwrap() {
+            let contents = fs::read_to_string(i.unwrap().path()).unwrap();
+            let parsed = parse(&contents).unwrap();
+            let raw = serialize(&parsed.0, &parsed.1);
+            assert_eq!(parse(&raw).unwrap(), parsed);
+            let codes = |raw: &str| {
+                json::parse(raw).unwrap()["lang"]
+                    .members()
+                    .map(|code| Lang::from(code.as_str().unwrap()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(codes(&raw), codes(&contents));
+        }
+    }
+    #[test]
+    fn parse_error_test() {
+        let error = |raw: &str| parse(&raw.to_string()).unwrap_err().to_string();
+        assert_eq!(
+            error("{\n\t\"lang\": [\"en\", \"fr\"],\n\t\"list\": [\n\t\t[\"yes\", oui]"),
+            "line 4, column 11: unexpected character 'o'"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"], "list": [["yes", "oui"]"#),
+            "line 1, column 47: unexpected end of file"
+        );
+        assert_eq!(
+            error(r#"{"lang": "en", "list": []}"#),
+            "\"lang\" is a string, expected a pair of languages"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"]}"#),
+            "\"list\" is missing, expected an array of entries"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"], "list": [["yes", "oui"], ["no", "non", 3]]}"#),
+            "entry 1: element 2 is a number, expected grammatical class"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"], "list": [["yes", "oui", "adv", 2]]}"#),
+            "entry 0: element 3 is a number, expected tags"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"], "list": [[["no", 0], "non"]]}"#),
+            "entry 0: element 0 is an array with a number, expected word"
+        );
+        assert_eq!(
+            error(r#"{"lang": ["en", "fr"], "list": ["yes"]}"#),
+            "entry 0 is a string, expected an array"
+        );
+    }
+    #[test]
+    fn lenient_test() {
+        let raw = String::from(
+            r#"{"lang": ["en", "fr"], "list": [
+                ["yes", "oui", "adv"],
+                ["no", "non", 3],
+                [["the work", 0, "the job"], "le travail", "noun"],
+                ["to rise", "s'élever", "verbe pronominal"],
+                "the rust"
+            ]}"#,
+        );
+        assert!(parse(&raw).is_err());
+        let (_, list, warnings) = parse_lenient(&raw).unwrap();
+        assert_eq!(
+            list,
+            vec![
+                Entry::new("yes", "oui", GramClass::Adverb),
+                Entry::new(
+                    Word::new_list(vec!["the work".into(), "the job".into()]),
+                    "le travail",
+                    GramClass::Noun
+                ),
+                Entry::new("to rise", "s'élever", GramClass::Other),
+            ]
+        );
+        let warnings = warnings.iter().map(Warning::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                "entry 1: element 2 is a number, expected grammatical class (skipped)",
+                "entry 2: element 0 is an array with a number, expected word (repaired)",
+                "entry 3: element 2 is \"verbe pronominal\", expected grammatical class (repaired)",
+                "entry 4 is a string, expected an array (skipped)",
+            ]
+        );
+        assert!(parse_lenient("{").is_err());
+    }
+    #[test]
     fn read_file_test() {
         for i in
             fs::read_dir("assets").expect("Failed to open assets files (should be in /assets/*)")
@@ -236,10 +878,88 @@ mod test {
             for i in database {
                 println!("{:?}", i);
                 for j in &i.0.base {
-                    assert_eq!(i.correct(&j, 0, &langs[1]), 1.0);
+                    assert_eq!(i.judge(&j, 0, &langs[1]).0, 1.0);
                 }
                 for j in &i.1.base {
-                    assert_eq!(i.correct(&j, 1, &langs[0]), 1.0);
+                    assert_eq!(i.judge(&j, 1, &langs[0]).0, 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn short_and_multibyte_words() {
+        let tolerance = Tolerance::default();
+        for lang in Lang::all() {
+            for class in GramClass::ALL {
+                for (right, answer) in [
+                    ("ox", "o"),
+                    ("Äpfel", "Ä"),
+                    ("der Äpfel", "dÄ"),
+                    ("é", "to"),
+                ] {
+                    let entry = Entry::new(right, "", class.clone());
+                    assert_eq!(entry.judge(right, 0, &lang).0, 1.);
+                    entry.correct_with(answer, 0, &lang, &tolerance);
+                }
+            }
+        }
+        assert_eq!(
+            rules::split_article("DIE Kraft", &german::ARTICLES),
+            Some(("die ", "Kraft"))
+        );
+        assert_eq!(rules::split_article("dÄ", &german::ARTICLES), None);
+    }
+
+    mod props {
+        use super::*;
+        use proptest::{prelude::*, sample::select};
+
+        // Short texts, often with articles, apostrophes and multibyte characters
+        fn text() -> impl Strategy<Value = String> {
+            prop_oneof![
+                "(der |die |the |to |l'|el |)[a-zäöüßéÄÖÜ’' ,¨-]{0,6}",
+                "\\PC{0,8}",
+            ]
+        }
+
+        fn word() -> impl Strategy<Value = Word> {
+            prop::collection::vec(text(), 1..3).prop_map(Word::new_list)
+        }
+
+        proptest! {
+            #[test]
+            fn correct_never_panics(
+                word in word(),
+                answer in text(),
+                lang in select(Lang::all()),
+                class in select(GramClass::ALL.to_vec()),
+                typos in 0..3usize,
+                accents: bool,
+            ) {
+                let entry = Entry(word, Word::default(), class, Meta::default());
+                let tolerance = Tolerance { typos, accents };
+                let (score, _) = entry.correct_with(&answer, 0, &lang, &tolerance);
+                prop_assert!((0. ..=1.).contains(&score));
+                let (score, _) = entry.correct_plural(&answer, 0, &tolerance);
+                prop_assert!((0. ..=1.).contains(&score));
+            }
+
+            #[test]
+            fn parse_never_panics(raw in text(), other in text()) {
+                let mut word: Word = (&format!("{} / {}", raw, other)).into();
+                word.split_plurals();
+                word.to_string();
+                german::plurals(&word);
+                german::plural_form(&raw, &other);
+                fuzzy::closest(&word, &other, &german::LANG, &GramClass::Noun);
+            }
+
+            #[test]
+            fn split_article_cuts_text(text in text()) {
+                if let Some((article, rest)) = rules::split_article(&text, &german::ARTICLES) {
+                    prop_assert!(text.ends_with(rest));
+                    prop_assert_eq!(text.chars().count(), article.chars().count() + rest.chars().count());
                 }
             }
         }
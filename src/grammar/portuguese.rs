@@ -0,0 +1,69 @@
+use super::{rules::LanguageRules, GramClass, Lang};
+
+pub struct Portuguese;
+
+pub const LANG: Lang = Lang::Known(&Portuguese);
+
+impl LanguageRules for Portuguese {
+    fn name(&self) -> &'static str {
+        "Português"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &[
+            "Portuguese",
+            "portuguese",
+            "português",
+            "Portugues",
+            "portugues",
+            "pt",
+            "pt_PT",
+            "pt_BR",
+        ]
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Noun => &["o ", "a ", "os ", "as "],
+            _ => &[],
+        }
+    }
+    // Reflexive verbs: "se lavar" for "lavar-se", "lavar" only gets half of the points
+    fn markers(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Verb => &["se "],
+            _ => &[],
+        }
+    }
+    fn endings(&self, gram_class: &GramClass) -> &[(&'static str, &'static str)] {
+        match gram_class {
+            GramClass::Verb => &[("-se", "")],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grammar::*;
+
+    #[test]
+    fn portuguese_noun() {
+        let noun = Entry::new("a força", "la force", GramClass::Noun);
+        assert_eq!(noun.judge("a força", 0, &portuguese::LANG).0, 1.);
+        assert_eq!(noun.judge("o força", 0, &portuguese::LANG).0, 0.5);
+        assert_eq!(noun.judge("força", 0, &portuguese::LANG).0, 0.5);
+        assert_eq!(noun.judge("as forças", 0, &portuguese::LANG).0, 0.);
+    }
+    #[test]
+    fn portuguese_verb() {
+        let verb = Entry::new("lavar-se", "se laver", GramClass::Verb);
+        assert_eq!(verb.judge("lavar-se", 0, &portuguese::LANG).0, 1.);
+        assert_eq!(verb.judge("se lavar", 0, &portuguese::LANG).0, 1.);
+        assert_eq!(
+            verb.judge("lavar", 0, &portuguese::LANG),
+            (0.5, Mark::MissingEnding("-se"))
+        );
+        assert_eq!(verb.judge("levar", 0, &portuguese::LANG).0, 0.);
+        let verb = Entry::new("chamar", "appeler", GramClass::Verb);
+        assert_eq!(verb.judge("chamar-se", 0, &portuguese::LANG).0, 0.);
+    }
+}
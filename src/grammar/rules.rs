@@ -0,0 +1,409 @@
+use super::{
+    accents::eq_ignore_case, english, french, german, italian, kind, portuguese, spanish,
+    syntax_error, GramClass, GramErr, Lang, Mark, Word,
+};
+use json::JsonValue;
+use std::{collections::HashMap, sync::RwLock};
+
+/// How the answers in a language are corrected, and how the language is named.
+pub trait LanguageRules: Sync {
+    /// Name shown in the app and written in the files.
+    fn name(&self) -> &'static str;
+
+    /// Other names and locale codes accepted in the files.
+    fn codes(&self) -> &[&'static str];
+
+    /// Articles that are part of the answer, written with their space or apostrophe:
+    /// an answer without it, or with another one, only gets half of the points.
+    fn articles(&self, _gram_class: &GramClass) -> &[&'static str] {
+        &[]
+    }
+
+    /// Whether another article is wrong, as it gives another gender.
+    fn gender(&self, _gram_class: &GramClass) -> bool {
+        false
+    }
+
+    /// Reflexive pronouns that are part of the verbs, like "se " in French: judged like the
+    /// articles, but named as such in the feedback.
+    fn pronouns(&self, _gram_class: &GramClass) -> &[&'static str] {
+        &[]
+    }
+
+    /// Words that can be left out, like "to " before the English verbs. An answer with another
+    /// one is wrong.
+    fn markers(&self, _gram_class: &GramClass) -> &[&'static str] {
+        &[]
+    }
+
+    /// Endings of the reflexive verbs, with what replaces them when left out: ("rse", "r") for
+    /// "lavarse". An answer without it only gets half of the points, unless a marker stands
+    /// for it, like in "se lavar" for "lavar-se".
+    fn endings(&self, _gram_class: &GramClass) -> &[(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Form in which the answers and the words are compared.
+    fn normalize(&self, text: &str) -> String {
+        text.replace('’', "'")
+    }
+
+    /// Score of `answer` with the reason of it: the best option of `word`, and among those
+    /// with the same score the one that explains the most.
+    fn judge(&self, word: &Word, answer: &str, gram_class: &GramClass) -> (f32, Mark) {
+        let answer = self.normalize(answer);
+        let markers = self.markers(gram_class);
+        let (given, answer) = split_marker(&answer, markers);
+        if answer.is_empty() {
+            return (0., Mark::Wrong);
+        }
+        let exact = |index| match index {
+            0 => (1., Mark::Exact),
+            _ => (1., Mark::Alternative(index)),
+        };
+        word.base
+            .iter()
+            .enumerate()
+            .map(|(index, right)| {
+                let right = self.normalize(right);
+                let (marker, right) = split_marker(&right, markers);
+                if eq_ignore_case(right, answer) {
+                    return match (marker, given) {
+                        _ if marker == given => exact(index),
+                        (Some(marker), None) => (1., Mark::WithoutMarker(marker.trim_end())),
+                        _ => (0., Mark::Wrong), // "a solution" for "the solution"
+                    };
+                }
+                for (ending, replacement) in self.endings(gram_class) {
+                    let Some(stem) = right.strip_suffix(ending) else {
+                        continue;
+                    };
+                    if eq_ignore_case(&format!("{}{}", stem, replacement), answer) {
+                        return match given {
+                            Some(pronoun) => (1., Mark::Proclitic(pronoun.trim_end())),
+                            None => (0.5, Mark::MissingEnding(ending)),
+                        };
+                    }
+                }
+                let (articles, pronouns) = (self.articles(gram_class), self.pronouns(gram_class));
+                if let Some((pronoun, verb)) = split_article(right, pronouns) {
+                    return match split_article(answer, pronouns) {
+                        Some((found, given)) if eq_ignore_case(verb, given) => (
+                            0.5,
+                            Mark::WrongPronoun(pronoun.trim_end(), found.trim_end()),
+                        ),
+                        None if eq_ignore_case(verb, answer) => {
+                            (0.5, Mark::MissingPronoun(pronoun.trim_end()))
+                        }
+                        _ => (0., Mark::Wrong),
+                    };
+                }
+                let Some((article, noun)) = split_article(right, articles) else {
+                    return (0., Mark::Wrong);
+                };
+                match split_article(answer, articles) {
+                    Some((found, given)) if eq_ignore_case(noun, given) => {
+                        let (article, found) = (article.trim_end(), found.trim_end());
+                        if self.gender(gram_class) {
+                            (0., Mark::Gender(article, found))
+                        } else {
+                            (0.5, Mark::WrongArticle(article, found))
+                        }
+                    }
+                    None if eq_ignore_case(noun, answer) => {
+                        (0.5, Mark::MissingArticle(article.trim_end()))
+                    }
+                    _ => (0., Mark::Wrong),
+                }
+            })
+            .fold((0., Mark::Wrong), |best, (score, mark)| {
+                if score > best.0 || (score == best.0 && best.1 == Mark::Wrong) {
+                    (score, mark)
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+/// The marker `text` starts with among `markers`, if any, and the rest of it.
+fn split_marker<'a>(text: &'a str, markers: &[&'static str]) -> (Option<&'static str>, &'a str) {
+    split_article(text, markers).map_or((None, text), |(marker, rest)| (Some(marker), rest))
+}
+
+/// The article `text` starts with among `articles`, and the rest of it.
+/// The case is ignored, and `text` is only cut between its characters.
+pub fn split_article<'a>(
+    text: &'a str,
+    articles: &[&'static str],
+) -> Option<(&'static str, &'a str)> {
+    articles.iter().find_map(|article| {
+        let length = article.chars().count();
+        let end = text
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(text.len()))
+            .nth(length)?;
+        let (prefix, rest) = text.split_at(end);
+        eq_ignore_case(prefix, article).then_some((*article, rest))
+    })
+}
+
+/// Rules of a language defined in a data file, see `load`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    pub name: &'static str,
+    pub codes: Vec<&'static str>,
+    pub articles: HashMap<GramClass, Vec<&'static str>>,
+    pub gender: Vec<GramClass>,
+    pub pronouns: HashMap<GramClass, Vec<&'static str>>,
+    pub markers: HashMap<GramClass, Vec<&'static str>>,
+    pub endings: HashMap<GramClass, Vec<(&'static str, &'static str)>>,
+    pub normalize: Vec<(&'static str, &'static str)>, // replaced before comparing
+}
+
+impl LanguageRules for Rules {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn codes(&self) -> &[&'static str] {
+        &self.codes
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        self.articles.get(gram_class).map_or(&[], Vec::as_slice)
+    }
+    fn gender(&self, gram_class: &GramClass) -> bool {
+        self.gender.contains(gram_class)
+    }
+    fn pronouns(&self, gram_class: &GramClass) -> &[&'static str] {
+        self.pronouns.get(gram_class).map_or(&[], Vec::as_slice)
+    }
+    fn markers(&self, gram_class: &GramClass) -> &[&'static str] {
+        self.markers.get(gram_class).map_or(&[], Vec::as_slice)
+    }
+    fn endings(&self, gram_class: &GramClass) -> &[(&'static str, &'static str)] {
+        self.endings.get(gram_class).map_or(&[], Vec::as_slice)
+    }
+    fn normalize(&self, text: &str) -> String {
+        let text = text.replace('’', "'");
+        self.normalize
+            .iter()
+            .fold(text, |text, (from, to)| text.replace(from, to))
+    }
+}
+
+/// Languages written in the app, adding one only takes a line here.
+const BUILT_IN: [Lang; 6] = [
+    english::LANG,
+    french::LANG,
+    german::LANG,
+    spanish::LANG,
+    italian::LANG,
+    portuguese::LANG,
+];
+
+/// Known languages: the built-in ones, then those loaded at runtime and kept until the end
+/// of the program.
+pub struct Registry(RwLock<Vec<&'static dyn LanguageRules>>);
+
+/// Languages of the app, see `config::load_languages`.
+pub static REGISTRY: Registry = Registry::new();
+
+impl Registry {
+    pub const fn new() -> Self {
+        Self(RwLock::new(Vec::new()))
+    }
+
+    pub fn all(&self) -> Vec<Lang> {
+        let loaded = self.0.read().map(|list| list.clone()).unwrap_or_default();
+        BUILT_IN
+            .into_iter()
+            .chain(loaded.into_iter().map(Lang::Known))
+            .collect()
+    }
+
+    /// The language named `name`, or with `name` as one of its codes.
+    /// "Other", as `Lang::default()` is written, gives it back.
+    pub fn find(&self, name: &str) -> Lang {
+        self.all()
+            .into_iter()
+            .find(|lang| {
+                lang.rules()
+                    .is_some_and(|rules| rules.name() == name || rules.codes().contains(&name))
+            })
+            .unwrap_or_else(|| match name {
+                "" | "Other" => Lang::default(),
+                _ => Lang::Other(name.to_string()),
+            })
+    }
+
+    /// Register the languages of `raw`, which replace those loaded before. It is a JSON array
+    /// like `[{"name": "Nederlands", "codes": ["nl"], "articles": {"noun": ["de ", "het "]},
+    /// "gender": ["noun"], "pronouns": {"verb": ["zich "]}, "markers": {"verb": ["te "]},
+    /// "endings": {"verb": [["en", ""]]}, "normalize": [["ĳ", "ij"]]}]`.
+    pub fn load(&self, raw: &str) -> Result<Vec<Lang>, GramErr> {
+        let data = json::parse(raw).map_err(|err| syntax_error(raw, err))?;
+        if !data.is_array() {
+            return Err(GramErr::Field {
+                name: "languages",
+                found: kind(&data),
+                expected: "an array",
+            });
+        }
+        let list = data
+            .members()
+            .map(parse_rules)
+            .collect::<Result<Vec<_>, _>>()?;
+        let list = list
+            .into_iter()
+            .map(|rules| &*Box::leak(Box::new(rules)) as &'static dyn LanguageRules)
+            .collect::<Vec<_>>();
+        if let Ok(mut loaded) = self.0.write() {
+            loaded.clone_from(&list);
+        }
+        Ok(list.into_iter().map(Lang::Known).collect())
+    }
+}
+
+fn parse_rules(raw: &JsonValue) -> Result<Rules, GramErr> {
+    let field = |name: &'static str, expected: &'static str| GramErr::Field {
+        name,
+        found: kind(&raw[name]),
+        expected,
+    };
+    let text = |value: &JsonValue| -> Option<&'static str> {
+        Some(Box::leak(value.as_str()?.to_string().into_boxed_str()))
+    };
+    let texts = |value: &JsonValue| -> Option<Vec<&'static str>> {
+        match value {
+            JsonValue::Array(values) => values.iter().map(text).collect(),
+            _ => None,
+        }
+    };
+    let class = |name: &str| -> Option<GramClass> {
+        let gram_class: GramClass = name.into();
+        (gram_class != GramClass::Other || ["other", "Other"].contains(&name)).then_some(gram_class)
+    };
+    let by_class = |name: &'static str| -> Result<HashMap<GramClass, Vec<&'static str>>, GramErr> {
+        raw[name]
+            .entries()
+            .map(|(key, value)| Some((class(key)?, texts(value)?)))
+            .collect::<Option<_>>()
+            .ok_or(field(name, "lists of texts by grammatical class"))
+    };
+
+    let name = text(&raw["name"]).ok_or(field("name", "a string"))?;
+    let codes = match &raw["codes"] {
+        JsonValue::Null => Vec::new(),
+        codes => texts(codes).ok_or(field("codes", "an array of strings"))?,
+    };
+    let gender = raw["gender"]
+        .members()
+        .map(|name| class(name.as_str()?))
+        .collect::<Option<_>>()
+        .ok_or(field("gender", "an array of grammatical classes"))?;
+    let pairs = |value: &JsonValue| -> Option<Vec<(&'static str, &'static str)>> {
+        value
+            .members()
+            .map(|pair| Some((text(&pair[0])?, text(&pair[1])?)))
+            .collect()
+    };
+    let endings = raw["endings"]
+        .entries()
+        .map(|(key, value)| Some((class(key)?, pairs(value)?)))
+        .collect::<Option<_>>()
+        .ok_or(field("endings", "pairs of texts by grammatical class"))?;
+    let normalize = pairs(&raw["normalize"]).ok_or(field("normalize", "pairs of texts"))?;
+    Ok(Rules {
+        name,
+        codes,
+        articles: by_class("articles")?,
+        gender,
+        pronouns: by_class("pronouns")?,
+        markers: by_class("markers")?,
+        endings,
+        normalize,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::Registry;
+    use crate::grammar::*;
+
+    #[test]
+    fn rules_test() {
+        let raw = r#"[{
+            "name": "Nederlands",
+            "codes": ["nl", "Dutch"],
+            "articles": {"noun": ["de ", "het "]},
+            "gender": ["noun"],
+            "markers": {"verb": ["zich "]},
+            "normalize": [["ĳ", "ij"]]
+        }]"#;
+        // Apart from the languages of the app
+        let registry = Registry::new();
+        let langs = registry.load(raw).unwrap();
+        assert_eq!(langs.len(), 1);
+        let dutch = registry.find("nl");
+        assert_eq!(dutch, langs[0]);
+        assert_eq!(dutch.to_string(), "Nederlands");
+        assert_eq!(registry.find("Nederlands"), dutch);
+        assert!(registry.all().contains(&dutch));
+        assert_eq!(registry.find("German"), german::LANG);
+        assert_eq!(Lang::from("nl"), Lang::Other("nl".into()));
+        assert_eq!(
+            Lang::from(Lang::default().to_string().as_str()),
+            Lang::default()
+        );
+
+        let noun = Entry::new("het huis", "la maison", GramClass::Noun);
+        assert_eq!(noun.judge("het huis", 0, &dutch).0, 1.);
+        assert_eq!(noun.judge("huis", 0, &dutch).0, 0.5);
+        assert_eq!(
+            noun.correct_with("de huis", 0, &dutch, &Tolerance::default()),
+            (0., Mark::Gender("het", "de"))
+        );
+        let verb = Entry::new("zich wassen", "se laver", GramClass::Verb);
+        assert_eq!(verb.judge("wassen", 0, &dutch).0, 1.);
+        let word = Entry::new("het ĳs", "la glace", GramClass::Noun);
+        assert_eq!(word.judge("het ijs", 0, &dutch).0, 1.);
+
+        assert_eq!(
+            registry
+                .load(r#"[{"name": "Svenska", "articles": {"pronoun": ["en "]}}]"#)
+                .unwrap_err()
+                .to_string(),
+            "\"articles\" is an object, expected lists of texts by grammatical class"
+        );
+        assert!(registry.load(r#"[{"codes": ["sv"]}]"#).is_err());
+    }
+    #[test]
+    fn reflexive_test() {
+        let cases = [
+            (spanish::LANG, "lavarse", "se lavar", "lavar"),
+            (italian::LANG, "lavarsi", "si lavare", "lavare"),
+            (portuguese::LANG, "lavar-se", "se lavar", "lavar"),
+        ];
+        for (lang, right, proclitic, bare) in cases {
+            let verb = Entry::new(right, "se laver", GramClass::Verb);
+            assert_eq!(verb.judge(right, 0, &lang).0, 1., "{}", lang);
+            let pronoun = proclitic.split(' ').next().unwrap();
+            assert_eq!(
+                verb.judge(proclitic, 0, &lang),
+                (1., Mark::Proclitic(pronoun)),
+                "{}",
+                lang
+            );
+            assert_eq!(verb.judge(bare, 0, &lang).0, 0.5, "{}", lang);
+            // Not a reflexive verb
+            let verb = Entry::new(bare, "laver", GramClass::Verb);
+            assert_eq!(
+                verb.judge(proclitic, 0, &lang),
+                (0., Mark::Wrong),
+                "{}",
+                lang
+            );
+        }
+    }
+}
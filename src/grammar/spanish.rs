@@ -0,0 +1,64 @@
+use super::{rules::LanguageRules, GramClass, Lang};
+
+pub struct Spanish;
+
+pub const LANG: Lang = Lang::Known(&Spanish);
+
+impl LanguageRules for Spanish {
+    fn name(&self) -> &'static str {
+        "Español"
+    }
+    fn codes(&self) -> &[&'static str] {
+        &[
+            "Spanish", "spanish", "español", "Espanol", "espanol", "es", "es_ES", "es_MX",
+        ]
+    }
+    fn articles(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Noun => &["el ", "la ", "los ", "las "],
+            _ => &[],
+        }
+    }
+    // Reflexive verbs: "se lavar" for "lavarse", "lavar" only gets half of the points
+    fn markers(&self, gram_class: &GramClass) -> &[&'static str] {
+        match gram_class {
+            GramClass::Verb => &["se "],
+            _ => &[],
+        }
+    }
+    fn endings(&self, gram_class: &GramClass) -> &[(&'static str, &'static str)] {
+        match gram_class {
+            GramClass::Verb => &[("rse", "r")],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grammar::*;
+
+    #[test]
+    fn spanish_noun() {
+        let noun = Entry::new("la fuerza", "la force", GramClass::Noun);
+        assert_eq!(noun.judge("la fuerza", 0, &spanish::LANG).0, 1.);
+        assert_eq!(noun.judge("el fuerza", 0, &spanish::LANG).0, 0.5);
+        assert_eq!(noun.judge("fuerza", 0, &spanish::LANG).0, 0.5);
+        let noun = Entry::new("los árboles", "les arbres", GramClass::Noun);
+        assert_eq!(noun.judge("las árboles", 0, &spanish::LANG).0, 0.5);
+        assert_eq!(noun.judge("los arboles", 0, &spanish::LANG).0, 0.);
+    }
+    #[test]
+    fn spanish_verb() {
+        let verb = Entry::new("lavarse", "se laver", GramClass::Verb);
+        assert_eq!(verb.judge("lavarse", 0, &spanish::LANG).0, 1.);
+        assert_eq!(
+            verb.judge("lavar", 0, &spanish::LANG),
+            (0.5, Mark::MissingEnding("rse"))
+        );
+        assert_eq!(verb.judge("se lavar", 0, &spanish::LANG).0, 1.);
+        assert_eq!(verb.judge("levar", 0, &spanish::LANG).0, 0.);
+        let verb = Entry::new("llamar", "appeler", GramClass::Verb);
+        assert_eq!(verb.judge("llamarse", 0, &spanish::LANG).0, 0.);
+    }
+}
@@ -2,16 +2,14 @@ use regex::Regex;
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct Word {
-    pub base: Vec<String>, // many options can be separated by /
-    pub desc: String,      // between [] in json, display to give context
+    pub base: Vec<String>,           // many options can be separated by /
+    pub desc: String,                // between [] in json, display to give context
+    pub plural: Vec<Option<String>>, // of each option, like `-¨e` in `die Kraft, -¨e`
 }
 
 impl Word {
     pub fn new(one: impl Into<String>) -> self::Word {
-        Word {
-            base: vec![one.into()],
-            ..Default::default()
-        }
+        Self::new_list(vec![one.into()])
     }
     pub fn new_list(list: impl Into<Vec<String>>) -> self::Word {
         Word {
@@ -19,28 +17,45 @@ impl Word {
             ..Default::default()
         }
     }
+    /// Read the plural written after each option, see `german::split_plurals`.
+    pub fn split_plurals(&mut self) {
+        (self.base, self.plural) = self.options().into_iter().map(split_plural).unzip();
+        if self.plural.iter().all(Option::is_none) {
+            self.plural.clear();
+        }
+    }
+    /// The options as written in the files, with their plural.
+    pub fn options(&self) -> Vec<String> {
+        self.base
+            .iter()
+            .enumerate()
+            .map(|(i, base)| match self.plural.get(i) {
+                Some(Some(plural)) => format!("{}, {}", base, plural),
+                _ => base.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Separate the plural written after a comma, which starts with `-` or `¨`.
+fn split_plural(option: String) -> (String, Option<String>) {
+    match option.rsplit_once(',') {
+        Some((base, plural)) if plural.trim_start().starts_with(['-', '¨']) => {
+            (base.trim_end().to_string(), Some(plural.trim().to_string()))
+        }
+        _ => (option, None),
+    }
 }
 
 impl std::fmt::Display for Word {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let len = self.base.len();
-        let mut string = String::new();
-        if len >= 2 {
-            for i in &self.base[..(len - 1)] {
-                string += format!("{} / ", i).as_str();
-            }
-        }
-        string += &self.base[&len - 1].as_str();
-        write!(f, "{}", string)
+        write!(f, "{}", self.options().join(" / "))
     }
 }
 
 impl Into<Word> for &str {
     fn into(self) -> Word {
-        Word {
-            base: vec![self.into()],
-            ..Default::default()
-        }
+        Word::new(self)
     }
 }
 
@@ -59,8 +74,10 @@ impl Into<Word> for &String {
         };
         let re_split = Regex::new(r"\s/\s").unwrap();
         let base: Vec<String> = re_split.split(base).map(|i| i.to_string()).collect();
-        word.base = base;
-        word
+        Word {
+            desc: word.desc,
+            ..Word::new_list(base)
+        }
     }
 }
 
@@ -70,7 +87,7 @@ impl Into<Word> for &json::short::Short {
     }
 }
 
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub enum GramClass {
     Adjectiv,
     Adverb,
@@ -80,6 +97,16 @@ pub enum GramClass {
     Other,
 }
 
+impl GramClass {
+    pub const ALL: [GramClass; 5] = [
+        GramClass::Noun,
+        GramClass::Verb,
+        GramClass::Adjectiv,
+        GramClass::Adverb,
+        GramClass::Other,
+    ];
+}
+
 impl Into<GramClass> for &str {
     fn into(self) -> GramClass {
         match self {
@@ -99,3 +126,16 @@ impl Into<GramClass> for &String {
         self.as_str().into()
     }
 }
+
+impl std::fmt::Display for GramClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            GramClass::Adjectiv => "adjectiv",
+            GramClass::Adverb => "adverb",
+            GramClass::Noun => "noun",
+            GramClass::Verb => "verb",
+            GramClass::Other => "other",
+        };
+        write!(f, "{}", string)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{config, grammar::GramClass, settings::Direction, Error};
+use json::JsonValue;
+use std::collections::HashMap;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// Result of one card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub entry: String, // as displayed by `Entry`
+    pub gram_class: GramClass,
+    pub score: f32,
+}
+
+/// A finished round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub date: u64,          // in seconds since the UNIX epoch
+    pub files: Vec<String>, // studied together, none for the default list
+    pub direction: Direction,
+    pub answers: Vec<Answer>,
+    pub duration: u64, // in seconds
+}
+
+impl Session {
+    pub fn accuracy(&self) -> f32 {
+        self.answers.iter().map(|answer| answer.score).sum::<f32>()
+            / self.answers.len().max(1) as f32
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let answers = self
+            .answers
+            .iter()
+            .map(|answer| {
+                json::array![
+                    answer.entry.as_str(),
+                    answer.gram_class.to_string(),
+                    answer.score
+                ]
+            })
+            .collect::<Vec<_>>();
+        json::object! {
+            date: self.date,
+            files: self.files.clone(),
+            direction: Into::<&str>::into(self.direction),
+            answers: answers,
+            duration: self.duration,
+        }
+    }
+
+    fn from_json(raw: &JsonValue) -> Option<Self> {
+        let mut answers = Vec::new();
+        for answer in raw["answers"].members() {
+            answers.push(Answer {
+                entry: answer[0].as_str()?.to_string(),
+                gram_class: answer[1].as_str()?.into(),
+                score: answer[2].as_f32()?,
+            });
+        }
+        Some(Self {
+            date: raw["date"].as_u64()?,
+            // "file" before several lists could be studied together
+            files: match raw["file"].as_str() {
+                Some(file) => vec![file.to_string()],
+                None => raw["files"]
+                    .members()
+                    .filter_map(|file| file.as_str().map(String::from))
+                    .collect(),
+            },
+            direction: Direction::ALL
+                .into_iter()
+                .find(|direction| raw["direction"] == Into::<&str>::into(*direction))
+                .unwrap_or_default(),
+            answers,
+            duration: raw["duration"].as_u64()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History(pub Vec<Session>);
+
+impl History {
+    pub fn load() -> Self {
+        let mut history = Self::default();
+        if let Some(data) = config::read("history").and_then(|raw| json::parse(&raw).ok()) {
+            history.0 = data.members().filter_map(Session::from_json).collect();
+        }
+        history
+    }
+
+    pub async fn save(raw: String) -> Result<(), Error> {
+        config::write("history", raw).await
+    }
+
+    pub fn to_json(&self) -> String {
+        JsonValue::from(self.0.iter().map(Session::to_json).collect::<Vec<_>>()).dump()
+    }
+
+    /// Entries answered wrong the most often, with their number of mistakes and answers.
+    pub fn most_missed(&self, count: usize) -> Vec<(&str, usize, usize)> {
+        let mut missed: HashMap<&str, (usize, usize)> = HashMap::new();
+        for answer in self.0.iter().flat_map(|session| &session.answers) {
+            let stats = missed.entry(answer.entry.as_str()).or_default();
+            stats.1 += 1;
+            if answer.score < 1.0 {
+                stats.0 += 1;
+            }
+        }
+        let mut missed = missed
+            .into_iter()
+            .filter(|(_, (mistakes, _))| *mistakes > 0)
+            .map(|(entry, (mistakes, total))| (entry, mistakes, total))
+            .collect::<Vec<_>>();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        missed.truncate(count);
+        missed
+    }
+
+    /// Mean score and number of answers of each grammatical class.
+    pub fn accuracy_by_class(&self) -> Vec<(GramClass, f32, usize)> {
+        GramClass::ALL
+            .into_iter()
+            .filter_map(|gram_class| {
+                let scores = self
+                    .0
+                    .iter()
+                    .flat_map(|session| &session.answers)
+                    .filter(|answer| answer.gram_class == gram_class)
+                    .map(|answer| answer.score)
+                    .collect::<Vec<_>>();
+                match scores.len() {
+                    0 => None,
+                    len => Some((gram_class, scores.iter().sum::<f32>() / len as f32, len)),
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
+
+/// `YYYY-MM-DD` of a UNIX time, see http://howardhinnant.github.io/date_algorithms.html
+pub fn date(time: u64) -> String {
+    let days = (time / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn history_test() {
+        let answer = |entry: &str, gram_class, score| Answer {
+            entry: entry.to_string(),
+            gram_class,
+            score,
+        };
+        let session = |answers| Session {
+            date: 1_700_000_000,
+            files: vec!["assets/english1.json".into(), "assets/english2.json".into()],
+            direction: Direction::Random,
+            answers,
+            duration: 42,
+        };
+        let history = History(vec![
+            session(vec![
+                answer("yes = oui", GramClass::Adverb, 1.),
+                answer("the work = le travail", GramClass::Noun, 0.5),
+            ]),
+            session(vec![
+                answer("the work = le travail", GramClass::Noun, 0.),
+                answer("the rust = la rouille", GramClass::Noun, 0.),
+            ]),
+        ]);
+        assert_eq!(history.0[0].accuracy(), 0.75);
+        assert_eq!(
+            history.most_missed(5),
+            vec![
+                ("the work = le travail", 2, 2),
+                ("the rust = la rouille", 1, 1)
+            ]
+        );
+        assert_eq!(
+            history.accuracy_by_class(),
+            vec![(GramClass::Noun, 0.5 / 3., 3), (GramClass::Adverb, 1., 1)]
+        );
+        let parsed = json::parse(&history.to_json()).unwrap();
+        let loaded = parsed.members().filter_map(Session::from_json).collect();
+        assert_eq!(History(loaded), history);
+        let old = json::parse(r#"{"date": 0, "file": "list.json", "answers": [], "duration": 0}"#);
+        assert_eq!(
+            Session::from_json(&old.unwrap()).unwrap().files,
+            vec!["list.json"]
+        );
+        assert_eq!(date(1_700_000_000), "2023-11-14");
+        assert_eq!(date(0), "1970-01-01");
+    }
+}
@@ -1,9 +1,13 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use history::{Answer, History};
 use iced::{
     alignment,
     keyboard::{self, Key},
-    widget::{button, column, container, progress_bar, row, text, text_input, Space},
+    time::{Duration, Instant},
+    widget::{
+        button, column, container, progress_bar, row, scrollable, text, text_input, Column, Space,
+    },
     Alignment, Element, Length, Pixels, Size, Task, Theme,
 };
 use iced_aw::{
@@ -11,16 +15,41 @@ use iced_aw::{
     menu::{self, Item},
 };
 use rand::{seq::SliceRandom, thread_rng};
-use std::{env, path::PathBuf, sync::Arc, vec};
+use review::Schedule;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::Arc,
+    vec,
+};
 
+mod choice;
+#[cfg(not(target_family = "wasm"))]
+mod cli;
+mod config;
+mod filter;
+use filter::Filter;
 mod grammar;
-use grammar::{parse, Entry, GramClass, Lang};
+mod history;
+use grammar::{
+    delimited::{self, Columns},
+    fuzzy, german, parse, parse_lenient, serialize, Entry, GramClass, GramErr, Lang, Lenient, Mark,
+    Tolerance,
+};
 mod editor;
+use editor::Edit;
+mod review;
 mod settings;
+use settings::{Direction, Sampling};
+mod statistics;
 mod style;
 use style::style_text;
 
 fn main() -> iced::Result {
+    #[cfg(not(target_family = "wasm"))]
+    if let Some(code) = cli::main(&env::args().skip(1).collect::<Vec<_>>()) {
+        std::process::exit(code);
+    }
     iced::application(App::title, App::update, App::view)
         .window(iced::window::Settings {
             size: Size::new(700., 400.),
@@ -38,12 +67,36 @@ struct App {
     screen: Screen,
     content: Vec<Entry>,
     order: Vec<usize>,
+    mistakes: Vec<usize>,
+    round: usize,
     current: Option<usize>,
+    element: usize,
     entry: String,
     error: Option<Error>,
-    file: Option<PathBuf>,
+    warnings: Vec<String>,
+    show_warnings: bool,
+    lenient: bool,
+    columns: Columns,
+    columns_spec: String, // as typed in the settings, maybe invalid
+    files: Vec<PathBuf>,  // studied together
+    header: bool,         // write the Anki header in the CSV and TSV files, see `delimited`
     langs: [Lang; 2],
+    direction: Direction,
+    tolerance: Tolerance,
+    multiple_choice: bool,
+    choices: Vec<usize>,
+    plurals: bool, // ask the German plurals after the nouns
+    plural: bool,  // the plural of the current card is asked
+    filter: Filter,
+    sampling: Sampling,
+    max_cards: u16, // 0 for every card
+    time_limit: u8, // in minutes, 0 for none
+    schedule: Schedule,
+    history: History,
+    answers: Vec<Answer>,
+    started: Instant,
     score: (f32, f32),
+    mark: Mark,
     length: usize,
     dark_theme: bool,
     font_size: Pixels,
@@ -53,48 +106,222 @@ struct App {
 
 impl App {
     fn init(&mut self, content: Vec<Entry>) {
+        self.content = content;
+        self.round = 1;
+        let mut order = match self.sampling {
+            Sampling::Due => self.schedule.order(&self.content, review::today()),
+            Sampling::Random => {
+                let mut order = (0..self.content.len()).collect::<Vec<_>>();
+                order.shuffle(&mut thread_rng());
+                order
+            }
+            Sampling::InOrder => (0..self.content.len()).collect(),
+            Sampling::Missed => self.schedule.weighted(&self.content),
+        };
+        order.retain(|index| self.filter.matches(&self.content[*index]));
+        if self.max_cards > 0 {
+            order.truncate(self.max_cards as usize);
+        }
+        self.start(order);
+    }
+
+    /// Whether the time limit of the round is reached.
+    fn time_up(&self) -> bool {
+        self.time_limit > 0 && self.started.elapsed().as_secs() >= self.time_limit as u64 * 60
+    }
+
+    /// Start a round asking the entries of `order`.
+    fn start(&mut self, order: Vec<usize>) {
         self.entry = String::new();
         self.current = Some(0);
-        self.content = content;
         self.score = (0.0, 0.0);
-        self.length = self.content.len();
-        let mut order = (0..self.length).collect::<Vec<usize>>();
-        order.shuffle(&mut thread_rng());
+        self.length = order.len();
         self.order = order;
+        self.mistakes = Vec::new();
+        self.answers = Vec::new();
+        self.started = Instant::now();
         self.screen = Screen::Main(State::WaitUserAnswer);
+        if self.order.is_empty() {
+            // nothing matches the filter
+            self.current = None;
+            self.screen = Screen::Main(State::End);
+        }
+        self.ask();
+    }
+
+    /// Choose the side asked for the current card, and the answers proposed for it.
+    fn ask(&mut self) {
+        self.plural = false;
+        self.element = self.direction.element();
+        self.choices = match self.current {
+            Some(nb) if nb < self.order.len() => {
+                choice::candidates(&self.content, self.order[nb], self.element)
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    fn correct(&mut self) -> Task<Message> {
+        let index = self.order[self.current.unwrap()];
+        let answer = self.entry.trim();
+        let (score, mark) = match self.plural {
+            true => self.content[index].correct_plural(answer, self.element, &self.tolerance),
+            false => self.content[index].correct_with(
+                answer,
+                self.element,
+                &self.langs[self.element],
+                &self.tolerance,
+            ),
+        };
+        self.grade(score, mark)
+    }
+
+    /// German element of the current card, when its plural is still to be asked.
+    fn plural_element(&self) -> Option<usize> {
+        let entry = &self.content[self.order[self.current?]];
+        let element = self.langs.iter().position(|lang| *lang == german::LANG)?;
+        (self.plurals
+            && !self.plural
+            && !self.multiple_choice
+            && entry.2 == GramClass::Noun
+            && !german::plurals(entry.word(element)).is_empty())
+        .then_some(element)
+    }
+
+    /// Text shown to ask the current card.
+    fn prompt(&self, nb: usize) -> String {
+        let entry = &self.content[self.order[nb]];
+        match self.plural {
+            true => format!("{} → plural", entry.word(self.element).base.join(" / ")),
+            false => entry.get(1 - self.element),
+        }
+    }
+
+    /// The current card, with the expected plural in place of the asked word if needed.
+    fn expected(&self, nb: usize) -> Entry {
+        let mut entry = self.content[self.order[nb]].clone();
+        if self.plural {
+            let plurals = german::plurals(entry.word(self.element));
+            entry.set(self.element, grammar::Word::new_list(plurals));
+        }
+        entry
+    }
+
+    /// Pick the `nb`th proposed answer in multiple-choice mode.
+    fn choose(&mut self, nb: usize) -> Task<Message> {
+        let index = self.order[self.current.unwrap()];
+        self.entry = self.content[self.choices[nb]].get(self.element);
+        match self.choices[nb] == index {
+            true => self.grade(1.0, Mark::Exact),
+            false => self.grade(0.0, Mark::Wrong),
+        }
     }
 
-    fn correct(&mut self) {
+    fn grade(&mut self, score: f32, mark: Mark) -> Task<Message> {
         let index = self.order[self.current.unwrap()];
-        self.score.0 = self.content[index].correct(&self.entry.trim().into(), 0, &self.langs[0]);
+        (self.score.0, self.mark) = (score, mark);
         self.score.1 += self.score.0;
+        self.answers.push(Answer {
+            entry: self.content[index].to_string(),
+            gram_class: self.content[index].2.clone(),
+            score: self.score.0,
+        });
+        if self.score.0 < 1.0 && !self.mistakes.contains(&index) {
+            self.mistakes.push(index);
+        }
         self.screen = Screen::Main(State::Correcting);
+        // Already graded by its singular, or earlier today when reviewing the mistakes
+        if !self.plural && self.round == 1 {
+            self.schedule
+                .grade(&self.content[index], self.score.0, review::today());
+        }
+        Task::none()
+    }
+
+    /// End the round, and save it with the review states of its entries.
+    fn finish(&mut self) -> Task<Message> {
+        self.screen = Screen::Main(State::End);
+        match self.round {
+            1 => Task::batch([self.record(), self.save_reviews()]),
+            _ => self.record(),
+        }
+    }
+
+    /// Write the review states next to each studied file, once at the end of the round so
+    /// that the writes don't overlap.
+    fn save_reviews(&self) -> Task<Message> {
+        Task::batch(self.files.iter().map(|file| {
+            let entries = self
+                .content
+                .iter()
+                .filter(|entry| entry.3.source.as_ref() == Some(file));
+            Task::perform(
+                review::save(review::path(file), self.schedule.subset(entries).to_json()),
+                Message::ScheduleSaved,
+            )
+        }))
+    }
+
+    /// The file studied, if there is only one.
+    fn file(&self) -> Option<&PathBuf> {
+        match self.files.as_slice() {
+            [file] => Some(file),
+            _ => None,
+        }
     }
 
-    fn next(&mut self) {
+    fn next(&mut self) -> Task<Message> {
         self.entry = String::new();
+        if let Some(element) = self.plural_element() {
+            self.plural = true;
+            self.element = element;
+            self.screen = Screen::Main(State::WaitUserAnswer);
+            return Task::none();
+        }
         match self.current {
+            Some(nb) if nb + 1 == self.order.len() || self.time_up() => return self.finish(),
             Some(nb) => {
-                self.current = if nb + 1 == self.content.len() {
-                    self.screen = Screen::Main(State::End);
-                    Some(nb)
-                } else {
-                    self.screen = Screen::Main(State::WaitUserAnswer);
-                    Some(nb + 1)
-                }
+                self.screen = Screen::Main(State::WaitUserAnswer);
+                self.current = Some(nb + 1);
+                self.ask();
             }
             None => (),
         }
+        Task::none()
+    }
+
+    /// Add the finished round to the history.
+    fn record(&mut self) -> Task<Message> {
+        self.history.0.push(history::Session {
+            date: history::now(),
+            files: self
+                .files
+                .iter()
+                .filter_map(|path| path.to_str().map(String::from))
+                .collect(),
+            direction: self.direction,
+            answers: self.answers.clone(),
+            duration: self.started.elapsed().as_secs(),
+        });
+        Task::perform(History::save(self.history.to_json()), Message::HistorySaved)
     }
 
     fn run() -> (Self, Task<Message>) {
-        if let Some(arg) = env::args().nth(1) {
-            (
-                Self::default(),
-                Task::perform(open_file(PathBuf::from(arg)), Message::FileOpened),
-            )
-        } else {
-            (Self::default(), Task::none())
+        let mut app = Self {
+            history: History::load(),
+            ..Self::default()
+        };
+        app.error = config::load_languages().err();
+        let last_file = app.load_config().filter(|path| path.exists());
+        match env::args().nth(1).map(PathBuf::from).or(last_file) {
+            Some(path) => {
+                let task = Task::perform(
+                    open_files(vec![path], app.lenient, app.columns.clone()),
+                    Message::FileOpened,
+                );
+                (app, task)
+            }
+            None => (app, Task::none()),
         }
     }
 }
@@ -102,12 +329,12 @@ impl App {
 impl Default for App {
     fn default() -> Self {
         let mut default_content = vec![
-            Entry("yes".into(), "oui".into(), GramClass::Adverb),
-            Entry("no".into(), "non".into(), GramClass::Adverb),
-            Entry("the work".into(), "le travail".into(), GramClass::Noun),
-            Entry("the rust".into(), "la rouille".into(), GramClass::Noun),
-            Entry("the solution".into(), "la solution".into(), GramClass::Noun),
-            Entry("to rise".into(), "s'élever".into(), GramClass::Verb),
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("no", "non", GramClass::Adverb),
+            Entry::new("the work", "le travail", GramClass::Noun),
+            Entry::new("the rust", "la rouille", GramClass::Noun),
+            Entry::new("the solution", "la solution", GramClass::Noun),
+            Entry::new("to rise", "s'élever", GramClass::Verb),
         ];
         default_content.shuffle(&mut thread_rng());
         Self {
@@ -116,12 +343,36 @@ impl Default for App {
             score: (0.0, 0.0),
             length: default_content.len(),
             order: (0..default_content.len()).collect(),
+            mistakes: Vec::new(),
+            round: 1,
             content: default_content,
             current: Some(0),
+            element: 0,
             entry: String::new(),
             error: None,
-            file: None,
+            warnings: Vec::new(),
+            show_warnings: false,
+            lenient: false,
+            columns: Columns::default(),
+            columns_spec: Columns::default().to_string(),
+            files: Vec::new(),
+            header: true,
             langs: ["English".into(), "French".into()],
+            direction: Direction::default(),
+            tolerance: Tolerance::default(),
+            multiple_choice: false,
+            choices: Vec::new(),
+            plurals: true,
+            plural: false,
+            filter: Filter::default(),
+            sampling: Sampling::default(),
+            max_cards: 0,
+            time_limit: 0,
+            schedule: Schedule::default(),
+            history: History::default(),
+            answers: Vec::new(),
+            started: Instant::now(),
+            mark: Mark::default(),
             dark_theme: true,
             font_size: Pixels(16.0),
             spacing: 5.0,
@@ -135,24 +386,58 @@ enum Message {
     DebugToggle,
     TextInputChanged(String),
     ChooseFile,
-    OpenFile(Result<PathBuf, Error>),
-    FileOpened(Result<(PathBuf, Arc<([Lang; 2], Vec<Entry>)>), Error>),
+    ChooseFolder,
+    OpenFiles(Result<Vec<PathBuf>, Error>),
+    FileOpened(Result<Arc<Opened>, Error>),
+    ScheduleLoaded(Schedule),
+    ScheduleSaved(Result<(), Error>),
+    Save,
+    SaveAs,
+    SaveFile(Result<PathBuf, Error>),
+    FileSaved(Result<PathBuf, Error>),
+    NewList,
     OpenEditor,
-    EditText(usize),
+    EditText(usize, usize),
+    GramClassSelected(usize, GramClass),
+    LangSelected(usize, Lang),
+    EditEntry(usize, Edit),
     EditorClosed(()),
+    OpenStatistics,
+    StatisticsClosed,
+    HistorySaved(Result<(), Error>),
     Correction,
+    Choose(usize),
     Next,
     Start,
+    ReviewMistakes,
+    Tick,
     Enter,
+    DismissError,
+    WarningsToggle,
+    SaveSettings,
+    SettingsSaved(Result<(), Error>),
     ThemeSelected,
     TextFontSizeChanged(f32),
     SpacingChanged(f32),
+    DirectionSelected(Direction),
+    TyposChanged(u8),
+    MaxCardsChanged(u16),
+    TimeLimitChanged(u8),
+    SamplingSelected(Sampling),
+    AccentsToggle,
+    MultipleChoiceToggle,
+    PluralsToggle,
+    ClassFilterToggle(GramClass),
+    TagFilterToggle(String),
+    LenientToggle,
+    ColumnsChanged(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Screen {
     Main(State),
     Editor(Vec<[text_input::Id; 2]>),
+    Statistics(State), // state of the main screen to go back to
 }
 
 impl Default for Screen {
@@ -163,9 +448,59 @@ impl Default for Screen {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Error {
-    IoError,
     DialogClosed,
-    ParseError,
+    Io {
+        path: Option<PathBuf>, // `None` for the app's own storage, see in src/config.rs
+        message: String,
+    },
+    Parse {
+        path: PathBuf,
+        error: GramErr,
+    },
+    Langs {
+        path: PathBuf,
+        found: [Lang; 2],
+        expected: [Lang; 2],
+    },
+}
+
+impl Error {
+    fn io(path: &Path, err: std::io::Error) -> Self {
+        Self::Io {
+            path: Some(path.to_path_buf()),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::DialogClosed => write!(f, "no file chosen"),
+            Error::Io {
+                path: Some(path),
+                message,
+            } => write!(f, "{}: {}", path.display(), message),
+            Error::Io {
+                path: None,
+                message,
+            } => write!(f, "{}", message),
+            Error::Parse { path, error } => write!(f, "{}: {}", path.display(), error),
+            Error::Langs {
+                path,
+                found,
+                expected,
+            } => write!(
+                f,
+                "{}: the languages are {} and {}, expected {} and {}",
+                path.display(),
+                found[0],
+                found[1],
+                expected[0],
+                expected[1]
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
@@ -178,107 +513,264 @@ enum State {
 
 impl App {
     fn title(&self) -> String {
-        match &self.file {
-            Some(path) => format!("{} — ULang ", path.to_str().unwrap_or("")),
-            None => String::from("ULang"),
+        match self.files.as_slice() {
+            [] => String::from("ULang"),
+            [path] => format!("{} — ULang ", path.to_str().unwrap_or("")),
+            files => format!(
+                "{} — ULang ",
+                files
+                    .iter()
+                    .filter_map(|path| path.file_name()?.to_str())
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            ),
         }
     }
 
     fn subscription(&self) -> iced::Subscription<Message> {
-        keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+        let keys = keyboard::on_key_press(|key, modifiers| match key.as_ref() {
             Key::Character("o") if modifiers.command() => Some(Message::ChooseFile), // Ctrl + o
+            Key::Character("s" | "S") if modifiers.command() => Some(match modifiers.shift() {
+                true => Message::SaveAs, // Ctrl + Shift + s
+                false => Message::Save,  // Ctrl + s
+            }),
+            Key::Character("n") if modifiers.command() => Some(Message::NewList), // Ctrl + n
             Key::Character("e") if modifiers.command() => Some(Message::OpenEditor), // Ctrl + e
-            Key::Named(keyboard::key::Named::Enter) => Some(Message::Enter),         // Enter
+            Key::Named(keyboard::key::Named::Enter) => Some(Message::Enter),      // Enter
+            Key::Character(nb @ ("1" | "2" | "3" | "4")) if !modifiers.command() => {
+                Some(Message::Choose(nb.parse::<usize>().ok()? - 1)) // 1 to 4
+            }
             _ => None,
-        })
+        });
+        // Ends a round limited in time, and moves its progress bar
+        let clock = match self.screen {
+            Screen::Main(State::WaitUserAnswer | State::Correcting) if self.time_limit > 0 => {
+                iced::time::every(Duration::from_secs(1)).map(|_| Message::Tick)
+            }
+            _ => iced::Subscription::none(),
+        };
+        iced::Subscription::batch([keys, clock])
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::DebugToggle => {
                 self.debug_layout = !self.debug_layout;
-                Task::none()
+                self.save_config()
             }
             Message::TextInputChanged(value) => {
-                let index = self.order[self.current.unwrap()];
-                match self.screen {
-                    Screen::Main(_) => self.entry = value,
-                    Screen::Editor(_) => self.content[index].0 = (&value).into(),
+                self.entry = value;
+                if let (Screen::Editor(_), Some(index)) = (&self.screen, self.current) {
+                    self.content[index].set(self.element, (&self.entry).into());
                 }
                 Task::none()
             }
-            Message::ChooseFile => Task::perform(choose_file(), Message::OpenFile),
-            Message::OpenFile(result) => match result {
-                Ok(path) => Task::perform(open_file(path), Message::FileOpened),
+            Message::ChooseFile => Task::perform(choose_files(), Message::OpenFiles),
+            Message::ChooseFolder => Task::perform(choose_folder(), Message::OpenFiles),
+            Message::OpenFiles(result) => match result {
+                Ok(paths) => Task::perform(
+                    open_files(paths, self.lenient, self.columns.clone()),
+                    Message::FileOpened,
+                ),
                 Err(Error::DialogClosed) => Task::none(),
                 Err(err) => {
                     self.error = Some(err);
                     Task::none()
                 }
             },
-            Message::FileOpened(result) => {
-                match result {
-                    Ok((path, content)) => {
-                        self.langs = content.0.clone();
-                        self.init(content.1.clone());
-                        self.file = Some(path);
-                        self.error = None;
-                    }
-                    Err(Error::DialogClosed) => (),
-                    Err(err) => self.error = Some(err),
+            Message::FileOpened(result) => match result {
+                Ok(opened) => {
+                    self.langs = opened.langs.clone();
+                    self.schedule = Schedule::default();
+                    self.filter.tags.clear();
+                    self.init(opened.content.clone());
+                    self.error = None;
+                    self.warnings = opened.warnings.clone();
+                    self.show_warnings = false;
+                    self.files = opened.files.clone();
+                    self.header = opened.header;
+                    let task = Task::perform(
+                        review::load(self.files.iter().map(|path| review::path(path)).collect()),
+                        Message::ScheduleLoaded,
+                    );
+                    Task::batch([task, self.save_config()])
+                }
+                Err(Error::DialogClosed) => Task::none(),
+                Err(err) => {
+                    self.error = Some(err);
+                    Task::none()
+                }
+            },
+            Message::ScheduleLoaded(schedule) => {
+                self.schedule = schedule;
+                self.init(self.content.clone());
+                Task::none()
+            }
+            Message::ScheduleSaved(result) => {
+                if let Err(err) = result {
+                    self.error = Some(err);
                 }
                 Task::none()
             }
+            Message::Save => match self.file() {
+                Some(path) => Task::done(Message::SaveFile(Ok(path.clone()))),
+                None => Task::done(Message::SaveAs),
+            },
+            Message::SaveAs => Task::perform(choose_save_file(), Message::SaveFile),
+            Message::SaveFile(result) => match result {
+                Ok(path) => {
+                    let raw = match delimited::delimiter(&path) {
+                        Some(delimiter) => delimited::serialize(
+                            &self.langs,
+                            &self.content,
+                            delimiter,
+                            &self.columns,
+                            self.header,
+                        ),
+                        None => serialize(&self.langs, &self.content),
+                    };
+                    Task::perform(save_file(path, raw), Message::FileSaved)
+                }
+                Err(Error::DialogClosed) => Task::none(),
+                Err(err) => {
+                    self.error = Some(err);
+                    Task::none()
+                }
+            },
+            Message::FileSaved(result) => match result {
+                Ok(path) => {
+                    for entry in &mut self.content {
+                        entry.3.source = Some(path.clone());
+                        entry.3.flipped = false; // written in the order of the session
+                    }
+                    self.files = vec![path];
+                    self.error = None;
+                    self.save_config()
+                }
+                Err(Error::DialogClosed) => Task::none(),
+                Err(err) => {
+                    self.error = Some(err);
+                    Task::none()
+                }
+            },
+            Message::NewList => {
+                self.content = vec![Entry::default()];
+                self.files = Vec::new();
+                self.header = true;
+                self.warnings = Vec::new();
+                self.schedule = Schedule::default();
+                self.open_editor();
+                Task::done(Message::EditText(0, 0))
+            }
             Message::OpenEditor => {
-                self.score = (0.0, 0.0);
-                self.current = None;
-                self.screen = Screen::Editor(
-                    self.content
-                        .iter()
-                        .map(|_| [text_input::Id::unique(), text_input::Id::unique()])
-                        .collect::<Vec<_>>(),
-                );
+                self.open_editor();
                 Task::none()
             }
-            Message::EditText(index) => {
+            Message::EditText(index, element) => {
                 self.current = Some(index);
-                iced::widget::text_input::focus(self.input_id.clone())
+                self.element = element;
+                self.entry = self.content[index].get(element);
+                match &self.screen {
+                    Screen::Editor(ids) => text_input::focus(ids[index][element].clone()),
+                    _ => Task::none(),
+                }
+            }
+            Message::GramClassSelected(index, gram_class) => {
+                self.content[index].2 = gram_class;
+                Task::none()
+            }
+            Message::LangSelected(element, lang) => {
+                self.langs[element] = lang;
+                Task::none()
+            }
+            Message::EditEntry(index, action) => self.edit_entry(index, action),
+            Message::OpenStatistics => {
+                if let Screen::Main(state) = self.screen {
+                    self.screen = Screen::Statistics(state);
+                }
+                Task::none()
+            }
+            Message::StatisticsClosed => {
+                if let Screen::Statistics(state) = self.screen {
+                    self.screen = Screen::Main(state);
+                }
+                Task::none()
+            }
+            Message::HistorySaved(result) => {
+                if let Err(err) = result {
+                    self.error = Some(err);
+                }
+                Task::none()
             }
             Message::EditorClosed(()) => {
-                self.screen = Screen::Main(State::WaitUserAnswer);
-                self.current = Some(0);
+                for entry in &mut self.content {
+                    german::split_plurals(entry, &self.langs);
+                }
+                self.init(self.content.clone());
                 Task::none()
             }
+            Message::Tick => match self.screen {
+                Screen::Main(State::WaitUserAnswer | State::Correcting) if self.time_up() => {
+                    self.finish()
+                }
+                _ => Task::none(),
+            },
             Message::Enter => {
+                let mut task = Task::none();
                 if let Screen::Main(state) = self.screen {
                     match state {
-                        State::WaitUserAnswer => self.correct(),
-                        State::Correcting => self.next(),
+                        State::WaitUserAnswer if !self.multiple_choice => task = self.correct(),
+                        State::Correcting => task = self.next(),
                         _ => (),
                     }
                 }
+                Task::batch([task, text_input::focus::<Message>(self.input_id.clone())])
+            }
+            Message::Correction if self.multiple_choice => Task::none(), // see `Choose`
+            Message::Correction => self.correct(),
+            Message::Choose(nb) => {
+                if self.multiple_choice
+                    && self.screen == Screen::Main(State::WaitUserAnswer)
+                    && nb < self.choices.len()
+                {
+                    self.choose(nb)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Next => Task::batch([
+                self.next(),
+                text_input::focus::<Message>(self.input_id.clone()),
+            ]),
+            Message::Start => {
+                self.init(self.content.clone());
+                text_input::focus::<Message>(self.input_id.clone())
+            }
+            Message::ReviewMistakes => {
+                let mut order = self.mistakes.clone();
+                order.shuffle(&mut thread_rng());
+                self.round += 1;
+                self.start(order);
                 text_input::focus::<Message>(self.input_id.clone())
             }
-            Message::Correction => {
-                self.correct();
+            Message::DismissError => {
+                self.error = None;
                 Task::none()
             }
-            Message::Next => {
-                self.next();
-                text_input::focus::<Message>(self.input_id.clone())
+            Message::WarningsToggle => {
+                self.show_warnings = !self.show_warnings;
+                Task::none()
             }
-            Message::Start => {
-                if let Some(_) = self.file {
-                    self.init(self.content.clone());
-                } else {
-                    self.init(App::default().content);
+            Message::SaveSettings => self.save_config(),
+            Message::SettingsSaved(result) => {
+                if let Err(err) = result {
+                    self.error = Some(err);
                 }
-                self.screen = Screen::Main(State::WaitUserAnswer);
-                text_input::focus::<Message>(self.input_id.clone())
+                Task::none()
             }
             Message::ThemeSelected => {
                 self.dark_theme = !self.dark_theme;
-                Task::none()
+                self.save_config()
             }
             Message::TextFontSizeChanged(new_size) => {
                 self.font_size.0 = new_size;
@@ -288,6 +780,62 @@ impl App {
                 self.spacing = new_spacing;
                 Task::none()
             }
+            Message::MaxCardsChanged(cards) => {
+                self.max_cards = cards;
+                Task::none()
+            }
+            Message::TimeLimitChanged(minutes) => {
+                self.time_limit = minutes;
+                Task::none()
+            }
+            Message::SamplingSelected(sampling) => {
+                self.sampling = sampling;
+                self.save_config()
+            }
+            Message::TyposChanged(typos) => {
+                self.tolerance.typos = typos as usize;
+                Task::none()
+            }
+            Message::AccentsToggle => {
+                self.tolerance.accents = !self.tolerance.accents;
+                self.save_config()
+            }
+            Message::ClassFilterToggle(class) => {
+                filter::toggle(&mut self.filter.classes, class);
+                self.init(self.content.clone());
+                Task::none()
+            }
+            Message::TagFilterToggle(tag) => {
+                filter::toggle(&mut self.filter.tags, tag);
+                self.init(self.content.clone());
+                Task::none()
+            }
+            Message::PluralsToggle => {
+                self.plurals = !self.plurals;
+                self.save_config()
+            }
+            Message::MultipleChoiceToggle => {
+                self.multiple_choice = !self.multiple_choice;
+                self.save_config()
+            }
+            Message::LenientToggle => {
+                self.lenient = !self.lenient;
+                self.save_config()
+            }
+            Message::ColumnsChanged(spec) => {
+                if let Some(columns) = Columns::parse(&spec) {
+                    self.columns = columns;
+                }
+                self.columns_spec = spec;
+                Task::none()
+            }
+            Message::DirectionSelected(direction) => {
+                self.direction = direction;
+                if self.screen == Screen::Main(State::WaitUserAnswer) {
+                    self.ask();
+                }
+                self.save_config()
+            }
         }
     }
 
@@ -295,6 +843,7 @@ impl App {
         let mut screen = match self.screen {
             Screen::Main(_) => self.screen_main(),
             Screen::Editor(_) => self.screen_editor(), // see in src/editor.rs
+            Screen::Statistics(_) => self.screen_statistics(), // see in src/statistics.rs
         };
         if self.debug_layout {
             screen = screen.explain(iced::Color::WHITE);
@@ -342,24 +891,44 @@ impl App {
                 .spacing(5.0)
         };
 
+        let new = button(text("New list").size(self.font_size))
+            .on_press(Message::NewList)
+            .style(style::header_button);
+
         let open = button(text("Open").size(self.font_size))
             .on_press(Message::ChooseFile)
             .style(style::header_button);
 
+        let open_folder = button(text("Open folder").size(self.font_size))
+            .on_press(Message::ChooseFolder)
+            .style(style::header_button);
+
         let editor = button(text("Edit").size(self.font_size))
             .on_press(Message::OpenEditor)
             .style(style::header_button);
 
+        let statistics = button(text("Statistics").size(self.font_size))
+            .on_press(Message::OpenStatistics)
+            .style(style::header_button);
+
         #[rustfmt::skip]
         let header = iced_aw::menu_bar!(
             (button(text("File").size(self.font_size))
                 .style(style::header_button), // see in src/style.rs
             {
                 menu_tpl(iced_aw::menu_items!(
+                    (new)
                     (open)
+                    (open_folder)
                     (editor)
+                    (statistics)
                 )).width(Length::Shrink)
             })
+            (button(text("Filter").size(self.font_size))
+                .style(style::header_button),
+            {
+                self.view_filter() // see in src/filter.rs
+            })
             (button(text("Settings").size(self.font_size))
                 .style(style::header_button),
             {
@@ -368,12 +937,14 @@ impl App {
         );
 
         // Main
-        let lang_one = style_text(text(self.langs[0].to_string()), self.font_size);
-        let lang_two = style_text(text(self.langs[1].to_string()), self.font_size);
+        let asked = self.element;
+        let shown = if self.plural { asked } else { 1 - asked };
+        let lang_one = style_text(text(self.langs[asked].to_string()), self.font_size);
+        let lang_two = style_text(text(self.langs[shown].to_string()), self.font_size);
 
         let known = style_text(
             text(match self.current {
-                Some(nb) if state != State::End => self.content[self.order[nb]].get(1),
+                Some(nb) if state != State::End => self.prompt(nb),
                 _ => "".into(),
             }),
             self.font_size,
@@ -389,21 +960,64 @@ impl App {
             .width(self.font_size * 4.0)
             .align_x(alignment::Horizontal::Center),
         )
-        .on_press(match state {
-            State::Correcting => Message::Next,
-            State::WaitUserAnswer => Message::Correction,
-            State::End => Message::Start,
+        .on_press_maybe(match state {
+            State::Correcting => Some(Message::Next),
+            State::WaitUserAnswer if self.multiple_choice => None, // answered by the choices
+            State::WaitUserAnswer => Some(Message::Correction),
+            State::End => Some(Message::Start),
         });
 
-        let error_log = text(match &self.error {
-            Some(err) => format!("{:?}: invalid file", err),
-            None => "".to_string(),
-        })
-        .size(self.font_size);
+        let error_log = self.error.as_ref().map(|err| {
+            row![
+                text(err.to_string())
+                    .size(self.font_size)
+                    .color(style::TextColor::Red)
+                    .width(Length::Fill),
+                button(text("Dismiss").size(self.font_size)).on_press(Message::DismissError),
+            ]
+            .spacing(self.spacing)
+            .align_y(Alignment::Center)
+        });
+
+        // Warnings of the lenient parsing
+        let warnings = (!self.warnings.is_empty()).then(|| {
+            let mut warnings = Column::new().spacing(self.spacing / 2.0).push(
+                row![
+                    text(match self.warnings.len() {
+                        1 => String::from("1 warning while reading the file"),
+                        nb => format!("{} warnings while reading the file", nb),
+                    })
+                    .size(self.font_size)
+                    .width(Length::Fill),
+                    button(
+                        text(if self.show_warnings { "Hide" } else { "Show" }).size(self.font_size)
+                    )
+                    .on_press(Message::WarningsToggle),
+                ]
+                .spacing(self.spacing)
+                .align_y(Alignment::Center),
+            );
+            if self.show_warnings {
+                let mut list = Column::new().spacing(self.spacing / 2.0);
+                for warning in &self.warnings {
+                    list = list.push(
+                        text(warning)
+                            .size(self.font_size)
+                            .color(style::TextColor::Red),
+                    );
+                }
+                warnings = warnings.push(scrollable(list).height(Length::Fill));
+            }
+            warnings
+        });
 
         // Main
         let mut variable = row![].padding(self.spacing).align_y(Alignment::Center);
+        let mut explanation = None; // why the answer got its score, under it
         match state {
+            State::WaitUserAnswer if self.multiple_choice => {
+                variable = variable.push(self.view_choices()); // see in src/choice.rs
+            }
             State::WaitUserAnswer => {
                 variable = variable.push({
                     text_input("Write your answer", &self.entry)
@@ -417,52 +1031,118 @@ impl App {
                 let nb = self
                     .current
                     .expect("ERROR: current index in the data base is set to None");
+                let expected = self.expected(nb);
                 if self.entry.trim().is_empty() {
                     variable = variable.push(style_text(
-                        text(self.content[self.order[nb]].get(0)).color(style::TextColor::Red),
+                        text(expected.get(asked)).color(style::TextColor::Red),
                         self.font_size,
                     ));
                 } else if self.score.0 != 1.0 {
+                    let answer = self.entry.trim();
+                    let word = expected.word(asked);
+                    let right = fuzzy::closest(word, answer, &self.langs[asked], &expected.2)
+                        .map_or("", |(_, right)| right);
                     variable = variable
-                        .push(style_text(
-                            text(&self.entry).color(style::TextColor::Red),
-                            self.font_size,
-                        ))
+                        .push(if self.multiple_choice {
+                            Element::from(style_text(
+                                text(answer).color(style::TextColor::Red),
+                                self.font_size,
+                            ))
+                        } else {
+                            Element::from(style::diff_text(
+                                &fuzzy::diff(right, answer),
+                                self.font_size,
+                            ))
+                        })
                         .push(Space::with_width(Length::Fixed(10.0)))
                         .push(style_text(
-                            text(self.content[self.order[nb]].get(0))
-                                .color(style::TextColor::Green),
+                            text(expected.get(asked)).color(style::TextColor::Green),
                             self.font_size,
                         ));
                 } else {
                     variable = variable.push(style_text(
-                        text(self.content[self.order[nb]].get(0)).color(style::TextColor::Green),
+                        text(expected.get(asked)).color(style::TextColor::Green),
                         self.font_size,
                     ));
                 }
                 variable = variable
                     .push(Space::with_width(Length::Fixed(10.0)))
                     .push(text(self.score.0).size(self.font_size));
+                if !matches!(self.mark, Mark::Exact | Mark::Wrong) {
+                    let color = if self.score.0 == 1.0 {
+                        style::TextColor::Green
+                    } else {
+                        style::TextColor::Red
+                    };
+                    explanation = Some(
+                        row![text(self.mark.to_string())
+                            .size(self.font_size)
+                            .color(color)]
+                        .padding([0.0, self.spacing]),
+                    );
+                }
             }
             _ => (),
         }
+        let variable = column![variable].push_maybe(explanation);
 
         // Score
         let current = self.current.unwrap_or(0);
-        let max = self.length - 1;
+        // Progress in time when the round is limited in time, in cards otherwise
+        let (progress, max) = match self.time_limit {
+            0 => (current as f32, self.length.saturating_sub(1).max(1) as f32),
+            minutes => (self.started.elapsed().as_secs_f32(), minutes as f32 * 60.0),
+        };
+        // Plurals are asked on top of the cards
+        let answered = self.answers.len() + (state == State::WaitUserAnswer) as usize;
         let score = text(format!(
             "{} / {}{}",
             self.score.1,
-            current + 1,
+            answered,
             if state == State::End {
-                format!(" ({:.2} / 20)", self.score.1 * 20.0 / (current + 1) as f32)
+                format!(
+                    " ({:.2} / 20)",
+                    self.score.1 * 20.0 / answered.max(1) as f32
+                )
             } else {
                 "".to_string()
             }
         ))
         .size(self.font_size);
-        let advancement = progress_bar(0.0..=max as f32, current as f32).height(7.0);
-        let advancement_text = text(format!("{} %", current * 100 / max)).size(self.font_size);
+        let advancement = progress_bar(0.0..=max, progress).height(7.0);
+        let advancement_text =
+            text(format!("{} %", (progress.min(max) * 100.0 / max) as usize)).size(self.font_size);
+
+        let review_button = (state == State::End && !self.mistakes.is_empty()).then(|| {
+            button(text("Review mistakes").size(self.font_size)).on_press(Message::ReviewMistakes)
+        });
+
+        // Summary of the round
+        let summary = if state == State::End {
+            let mut summary = Column::new().spacing(self.spacing / 2.0).push(
+                text(match self.mistakes.len() {
+                    _ if self.order.is_empty() => String::from("No entry matches the filter"),
+                    0 => format!("Round {}: everything is correct", self.round),
+                    1 => format!("Round {}: 1 mistake", self.round),
+                    nb => format!("Round {}: {} mistakes", self.round, nb),
+                })
+                .size(self.font_size),
+            );
+            for index in &self.mistakes {
+                summary = summary.push(
+                    text(format!(
+                        "{} = {}",
+                        self.content[*index].get(0),
+                        self.content[*index].get(1)
+                    ))
+                    .size(self.font_size)
+                    .color(style::TextColor::Red),
+                );
+            }
+            Element::from(scrollable(summary).height(Length::Fill))
+        } else {
+            Element::from(Space::with_height(Length::Fill))
+        };
 
         // Final
         let grid = column![
@@ -474,30 +1154,82 @@ impl App {
                 .column_widths(&[Length::Shrink, Length::Fill])
                 .width(Length::Fill),
             // Score
-            row![Space::with_width(Length::Fill), score, next_button,]
+            row![Space::with_width(Length::Fill), score]
+                .push_maybe(review_button)
+                .push(next_button)
                 .spacing(self.spacing / 2.0)
                 .align_y(Alignment::Center),
             row![advancement, advancement_text,]
                 .spacing(self.spacing)
                 .align_y(Alignment::Center),
-            Space::with_height(Length::Fill),
+            summary,
         ]
-        .push_maybe(match &self.error {
-            Some(_) => Some(error_log),
-            None => None,
-        })
+        .push_maybe(error_log)
+        .push_maybe(warnings)
         .spacing(self.spacing);
         Element::from(grid)
     }
 }
 
 #[cfg(not(target_family = "wasm"))]
-async fn choose_file() -> Result<PathBuf, Error> {
-    let opt_handle = rfd::AsyncFileDialog::new()
-        .set_title("Choose a json file...")
+async fn choose_files() -> Result<Vec<PathBuf>, Error> {
+    let opt_handles = rfd::AsyncFileDialog::new()
+        .set_title("Choose vocabulary files...")
+        .add_filter(
+            "Vocabulary (*.json, *.csv, *.tsv, *.txt)",
+            &["json", "csv", "tsv", "txt"],
+        )
         .add_filter("Json (*.json)", &["json"])
+        .add_filter("CSV, TSV (*.csv, *.tsv, *.txt)", &["csv", "tsv", "txt"])
         .add_filter("All files (*.*)", &["*"])
-        .pick_file()
+        .pick_files()
+        .await;
+    match opt_handles {
+        Some(handles) => Ok(handles.into_iter().map(PathBuf::from).collect()),
+        None => Err(Error::DialogClosed),
+    }
+}
+
+/// Every vocabulary file of a folder, without the review states.
+#[cfg(not(target_family = "wasm"))]
+async fn choose_folder() -> Result<Vec<PathBuf>, Error> {
+    let dir: PathBuf = match rfd::AsyncFileDialog::new()
+        .set_title("Choose a folder...")
+        .pick_folder()
+        .await
+    {
+        Some(handle) => handle.into(),
+        None => return Err(Error::DialogClosed),
+    };
+    let mut files = std::fs::read_dir(&dir)
+        .map_err(|err| Error::io(&dir, err))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            let name = path.to_str().unwrap_or("");
+            path.is_file()
+                && (delimited::delimiter(path).is_some() || name.ends_with(".json"))
+                && !name.ends_with(".review.json")
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    match files.is_empty() {
+        true => Err(Error::Io {
+            path: Some(dir),
+            message: String::from("no vocabulary file in this folder"),
+        }),
+        false => Ok(files),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn choose_save_file() -> Result<PathBuf, Error> {
+    let opt_handle = rfd::AsyncFileDialog::new()
+        .set_title("Save as...")
+        .add_filter("Json (*.json)", &["json"])
+        .add_filter("CSV (*.csv)", &["csv"])
+        .add_filter("TSV (*.tsv, *.txt)", &["tsv", "txt"])
+        .set_file_name("vocabulary.json")
+        .save_file()
         .await;
     match opt_handle {
         Some(handle) => Ok(handle.into()),
@@ -505,12 +1237,228 @@ async fn choose_file() -> Result<PathBuf, Error> {
     }
 }
 
-async fn open_file(path: PathBuf) -> Result<(PathBuf, Arc<([Lang; 2], Vec<Entry>)>), Error> {
-    match async_std::fs::read_to_string(path.as_path()).await {
-        Ok(raw) => match parse(&raw) {
-            Ok(data) => Ok((path.into(), Arc::new(data))),
-            Err(_) => Err(Error::ParseError),
-        },
-        Err(_) => Err(Error::IoError),
+async fn save_file(path: PathBuf, raw: String) -> Result<PathBuf, Error> {
+    match async_std::fs::write(path.as_path(), raw).await {
+        Ok(()) => Ok(path),
+        Err(err) => Err(Error::io(&path, err)),
+    }
+}
+
+/// Read a JSON file, or a CSV or TSV one according to its extension.
+fn parse_file(
+    path: &Path,
+    raw: &String,
+    lenient: bool,
+    columns: &Columns,
+) -> Result<Lenient, GramErr> {
+    match delimited::delimiter(path) {
+        Some(delimiter) => delimited::parse(raw, delimiter, columns, lenient),
+        None if lenient => parse_lenient(raw),
+        None => parse(raw).map(|(langs, list)| (langs, list, Vec::new())),
+    }
+}
+
+/// Lists studied together, see `open_files`.
+#[derive(Debug)]
+struct Opened {
+    files: Vec<PathBuf>,
+    header: bool, // the first file is not a CSV or TSV one without the header of Anki
+    langs: [Lang; 2],
+    content: Vec<Entry>,
+    warnings: Vec<String>,
+}
+
+/// Read several lists as one. Their languages must be the same as the first one's,
+/// or swapped, in which case their entries are flipped.
+async fn open_files(
+    files: Vec<PathBuf>,
+    lenient: bool,
+    columns: Columns,
+) -> Result<Arc<Opened>, Error> {
+    let mut langs: Option<[Lang; 2]> = None;
+    let mut header = true;
+    let mut content = Vec::new();
+    let mut warnings = Vec::new();
+    for path in &files {
+        let raw = async_std::fs::read_to_string(path)
+            .await
+            .map_err(|err| Error::io(path, err))?;
+        let (found, mut list, file_warnings) =
+            parse_file(path, &raw, lenient, &columns).map_err(|error| Error::Parse {
+                path: path.clone(),
+                error,
+            })?;
+        match &langs {
+            None => {
+                header = delimited::delimiter(path).is_none() || delimited::has_header(&raw);
+                langs = Some(found);
+            }
+            Some(expected) if *expected == found => (),
+            Some(expected) if expected[0] == found[1] && expected[1] == found[0] => {
+                for entry in &mut list {
+                    std::mem::swap(&mut entry.0, &mut entry.1);
+                    entry.3.flipped = true;
+                }
+            }
+            Some(expected) => {
+                return Err(Error::Langs {
+                    path: path.clone(),
+                    found,
+                    expected: expected.clone(),
+                })
+            }
+        }
+        for entry in &mut list {
+            entry.3.source = Some(path.clone());
+        }
+        content.append(&mut list);
+        warnings.extend(file_warnings.iter().map(|warning| match files.len() {
+            1 => warning.to_string(),
+            _ => format!("{}: {}", path.display(), warning),
+        }));
+    }
+    Ok(Arc::new(Opened {
+        langs: langs.unwrap_or_default(),
+        files,
+        header,
+        content,
+        warnings,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use grammar::{english, french};
+
+    #[test]
+    fn open_files_test() {
+        let dir = env::temp_dir().join(format!("u-lang-open-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, langs: &str, list: &str| {
+            let path = dir.join(name);
+            let raw = format!(r#"{{"lang": [{}], "list": [{}]}}"#, langs, list);
+            std::fs::write(&path, raw).unwrap();
+            path
+        };
+        let first = write("first.json", r#""en", "fr""#, r#"["yes", "oui"]"#);
+        let swapped = write("swapped.json", r#""fr", "en""#, r#"["non", "no"]"#);
+        let other = write("other.json", r#""de", "fr""#, r#"["ja", "oui"]"#);
+        let open = |files| async_std::task::block_on(open_files(files, false, Columns::default()));
+
+        let Ok(opened) = open(vec![first.clone(), swapped.clone()]) else {
+            panic!("the lists should open together")
+        };
+        assert_eq!(opened.langs, [english::LANG, french::LANG]);
+        assert_eq!(opened.content.len(), 2);
+        let flipped = &opened.content[1];
+        assert_eq!(flipped.to_string(), "no = non");
+        assert!(flipped.3.flipped && !opened.content[0].3.flipped);
+        assert_eq!(flipped.3.source.as_ref(), Some(&swapped));
+
+        // Reviews are stored in the order of the file, as when it is opened alone
+        let mut schedule = review::Schedule::default();
+        schedule.grade(flipped, 1., 10);
+        let Ok(alone) = open(vec![swapped.clone()]) else {
+            panic!("the list should open")
+        };
+        let saved = review::Schedule::from_json(&schedule.subset(opened.content.iter()).to_json());
+        assert_eq!(saved.get(&alone.content[0]), schedule.get(flipped));
+
+        match open(vec![first, other.clone()]) {
+            Err(Error::Langs {
+                path,
+                found,
+                expected,
+            }) => {
+                assert_eq!(path, other);
+                assert_eq!(found[0], Lang::from("de"));
+                assert_eq!(expected, [english::LANG, french::LANG]);
+            }
+            _ => panic!("the languages should not match"),
+        }
+        assert!(opened.header);
+        let plain = dir.join("plain.csv");
+        std::fs::write(&plain, "yes,oui\n").unwrap();
+        let Ok(opened) = open(vec![plain]) else {
+            panic!("the list should open")
+        };
+        assert!(!opened.header);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn session_limits_test() {
+        let content = vec![
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("no", "non", GramClass::Adverb),
+            Entry::new("the work", "le travail", GramClass::Noun),
+        ];
+        let mut app = App {
+            langs: [english::LANG, french::LANG],
+            sampling: Sampling::InOrder,
+            max_cards: 2,
+            ..App::default()
+        };
+        app.init(content.clone());
+        assert_eq!((app.order.clone(), app.length), (vec![0, 1], 2));
+        app.max_cards = 0;
+        app.init(content.clone());
+        assert_eq!(app.order, vec![0, 1, 2]);
+
+        // The round ends on the clock once the time is up, or on the next card
+        let minute_ago = || Instant::now().checked_sub(Duration::from_secs(60)).unwrap();
+        app.started = minute_ago();
+        let _ = app.update(Message::Tick);
+        assert_eq!(app.screen, Screen::Main(State::WaitUserAnswer));
+        app.time_limit = 1;
+        app.init(content.clone());
+        let _ = app.update(Message::Tick);
+        assert_eq!(app.screen, Screen::Main(State::WaitUserAnswer));
+        app.started = minute_ago();
+        let _ = app.update(Message::Tick);
+        assert_eq!(app.screen, Screen::Main(State::End));
+        app.init(content);
+        let _ = app.next();
+        assert_eq!(app.current, Some(1));
+        app.started = minute_ago();
+        let _ = app.next();
+        assert_eq!(app.screen, Screen::Main(State::End));
+    }
+    #[test]
+    fn multiple_choice_test() {
+        let content = vec![
+            Entry::new("yes", "oui", GramClass::Adverb),
+            Entry::new("no", "non", GramClass::Adverb),
+        ];
+        let mut app = App {
+            langs: [english::LANG, french::LANG],
+            sampling: Sampling::InOrder,
+            multiple_choice: true,
+            ..App::default()
+        };
+        app.init(content);
+        // Only the choices answer, not the empty input
+        let _ = app.update(Message::Correction);
+        assert_eq!(app.screen, Screen::Main(State::WaitUserAnswer));
+        assert!(app.answers.is_empty());
+    }
+    #[test]
+    fn review_mistakes_test() {
+        let content = vec![Entry::new("yes", "oui", GramClass::Adverb)];
+        let mut app = App {
+            langs: [english::LANG, french::LANG],
+            ..App::default()
+        };
+        app.init(content.clone());
+        let _ = app.grade(0., Mark::Wrong);
+        let _ = app.next();
+        assert_eq!(app.screen, Screen::Main(State::End));
+        // Right on the same day, but the entry stays graded as missed
+        let _ = app.update(Message::ReviewMistakes);
+        let _ = app.grade(1., Mark::Exact);
+        let _ = app.next();
+        assert_eq!(app.round, 2);
+        let review = app.schedule.get(&content[0]);
+        assert_eq!((review.reviews, review.streak, review.interval), (1, 0, 1));
     }
 }
@@ -1,7 +1,74 @@
-use iced::widget::{slider, text, toggler};
+use iced::widget::{radio, slider, text, text_input, toggler};
 use iced_aw::menu;
+use rand::{thread_rng, Rng};
 
-use crate::{App, Message, Theme};
+use crate::{grammar::delimited::Columns, App, Message, Theme};
+
+/// Which side of the entries the user is asked for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    SecondToFirst,
+    FirstToSecond,
+    Random,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 3] = [
+        Direction::SecondToFirst,
+        Direction::FirstToSecond,
+        Direction::Random,
+    ];
+
+    /// Element of the entry to answer, drawn again for each card in `Random` mode.
+    pub fn element(&self) -> usize {
+        match self {
+            Direction::SecondToFirst => 0,
+            Direction::FirstToSecond => 1,
+            Direction::Random => thread_rng().gen_range(0..2),
+        }
+    }
+}
+
+impl From<Direction> for &str {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::SecondToFirst => "second_to_first",
+            Direction::FirstToSecond => "first_to_second",
+            Direction::Random => "random",
+        }
+    }
+}
+
+/// How the cards of a session are chosen among the entries.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    #[default]
+    Due, // see `Schedule::order`
+    Random,
+    InOrder,
+    Missed, // see `Schedule::weighted`
+}
+
+impl Sampling {
+    pub const ALL: [Sampling; 4] = [
+        Sampling::Due,
+        Sampling::Random,
+        Sampling::InOrder,
+        Sampling::Missed,
+    ];
+}
+
+impl From<Sampling> for &str {
+    fn from(sampling: Sampling) -> Self {
+        match sampling {
+            Sampling::Due => "due",
+            Sampling::Random => "random",
+            Sampling::InOrder => "in_order",
+            Sampling::Missed => "missed",
+        }
+    }
+}
 
 impl App {
     pub fn view_settings(&self) -> menu::Menu<'_, Message, Theme, iced::Renderer> {
@@ -19,10 +86,100 @@ impl App {
             .text_size(self.font_size);
 
         let font_size_header = text("Text size").size(self.font_size);
-        let font_size_slidder = slider(10.0..=50.0, self.font_size.0, Message::TextFontSizeChanged);
+        let font_size_slidder = slider(10.0..=50.0, self.font_size.0, Message::TextFontSizeChanged)
+            .on_release(Message::SaveSettings);
 
         let spacing_header = text("Spacing").size(self.font_size);
-        let spacing_slider = slider(0.0..=20.0, self.spacing, Message::SpacingChanged);
+        let spacing_slider = slider(0.0..=20.0, self.spacing, Message::SpacingChanged)
+            .on_release(Message::SaveSettings);
+
+        let typos_header =
+            text(format!("Typos allowed: {}", self.tolerance.typos)).size(self.font_size);
+        let typos_slider = slider(0..=3, self.tolerance.typos as u8, Message::TyposChanged)
+            .on_release(Message::SaveSettings);
+
+        let accents = toggler(self.tolerance.accents)
+            .label("Ignore accents")
+            .on_toggle(|_| Message::AccentsToggle)
+            .size(self.font_size)
+            .text_size(self.font_size);
+
+        let multiple_choice = toggler(self.multiple_choice)
+            .label("Multiple choice")
+            .on_toggle(|_| Message::MultipleChoiceToggle)
+            .size(self.font_size)
+            .text_size(self.font_size);
+
+        let plurals = toggler(self.plurals)
+            .label("Ask German plurals")
+            .on_toggle(|_| Message::PluralsToggle)
+            .size(self.font_size)
+            .text_size(self.font_size);
+
+        let lenient = toggler(self.lenient)
+            .label("Skip invalid entries")
+            .on_toggle(|_| Message::LenientToggle)
+            .size(self.font_size)
+            .text_size(self.font_size);
+
+        let columns_header = text(match Columns::parse(&self.columns_spec) {
+            Some(_) => "CSV columns",
+            None => "CSV columns (invalid)",
+        })
+        .size(self.font_size);
+        let columns = text_input("first,second,class", &self.columns_spec)
+            .on_input(Message::ColumnsChanged)
+            .on_submit(Message::SaveSettings)
+            .size(self.font_size);
+
+        let direction_header = text("Direction").size(self.font_size);
+        let direction = |label: String, value| {
+            radio(
+                label,
+                value,
+                Some(self.direction),
+                Message::DirectionSelected,
+            )
+            .size(self.font_size)
+            .text_size(self.font_size)
+        };
+        let second_to_first = direction(
+            format!("{} → {}", self.langs[1], self.langs[0]),
+            Direction::SecondToFirst,
+        );
+        let first_to_second = direction(
+            format!("{} → {}", self.langs[0], self.langs[1]),
+            Direction::FirstToSecond,
+        );
+        let random = direction(String::from("Both"), Direction::Random);
+
+        let cards_header = text(match self.max_cards {
+            0 => String::from("Cards: all"),
+            cards => format!("Cards: {}", cards),
+        })
+        .size(self.font_size);
+        let cards_slider = slider(0..=100, self.max_cards, Message::MaxCardsChanged)
+            .step(5u16)
+            .on_release(Message::SaveSettings);
+
+        let time_header = text(match self.time_limit {
+            0 => String::from("Time limit: none"),
+            minutes => format!("Time limit: {} min", minutes),
+        })
+        .size(self.font_size);
+        let time_slider = slider(0..=30, self.time_limit, Message::TimeLimitChanged)
+            .on_release(Message::SaveSettings);
+
+        let sampling_header = text("Cards asked").size(self.font_size);
+        let sampling = |label, value| {
+            radio(label, value, Some(self.sampling), Message::SamplingSelected)
+                .size(self.font_size)
+                .text_size(self.font_size)
+        };
+        let due = sampling("Due first", Sampling::Due);
+        let shuffled = sampling("Random", Sampling::Random);
+        let in_order = sampling("File order", Sampling::InOrder);
+        let missed = sampling("Often missed", Sampling::Missed);
 
         let debug_layout = toggler(self.debug_layout)
             .label("Debug layout")
@@ -37,6 +194,27 @@ impl App {
             (font_size_slidder)
             (spacing_header)
             (spacing_slider)
+            (typos_header)
+            (typos_slider)
+            (accents)
+            (multiple_choice)
+            (plurals)
+            (lenient)
+            (columns_header)
+            (columns)
+            (direction_header)
+            (second_to_first)
+            (first_to_second)
+            (random)
+            (cards_header)
+            (cards_slider)
+            (time_header)
+            (time_slider)
+            (sampling_header)
+            (due)
+            (shuffled)
+            (in_order)
+            (missed)
             (debug_layout)
         ));
         settings
@@ -0,0 +1,116 @@
+use crate::{history, style, App, Message};
+use iced::{
+    widget::{button, column, progress_bar, row, scrollable, text, Column},
+    Alignment, Element, Length,
+};
+use iced_aw::menu::{self, Item};
+use std::path::Path;
+
+impl App {
+    pub fn screen_statistics(&self) -> Element<'_, Message> {
+        // Header
+        let menu_tpl = |items| {
+            menu::Menu::new(items)
+                .max_width(180.0)
+                .offset(5.0)
+                .spacing(5.0)
+        };
+
+        let close = button(text("Close").size(self.font_size))
+            .on_press(Message::StatisticsClosed)
+            .style(style::header_button);
+
+        #[rustfmt::skip]
+        let header = iced_aw::menu_bar!(
+            (button(text("File").size(self.font_size))
+                .style(style::header_button), // see in src/style.rs
+            {
+                menu_tpl(iced_aw::menu_items!(
+                    (close)
+                )).width(Length::Shrink)
+            })
+            (button(text("Settings").size(self.font_size))
+                .style(style::header_button),
+            {
+                self.view_settings() // see in src/settings.rs
+            })
+        );
+
+        // Main
+        let title = |label| text(label).size(self.font_size * 1.2);
+        let bar = |label: String, value: f32, info: String| {
+            row![
+                text(label)
+                    .size(self.font_size)
+                    .width(Length::FillPortion(3)),
+                progress_bar(0.0..=1.0, value)
+                    .height(7.0)
+                    .width(Length::FillPortion(3)),
+                text(format!("{:.0} %", value * 100.0))
+                    .size(self.font_size)
+                    .width(Length::FillPortion(1)),
+                text(info)
+                    .size(self.font_size)
+                    .width(Length::FillPortion(2)),
+            ]
+            .spacing(self.spacing)
+            .align_y(Alignment::Center)
+        };
+
+        let mut main = Column::new().spacing(self.spacing).padding(self.spacing);
+        if self.history.0.is_empty() {
+            main = main.push(text("No session recorded yet").size(self.font_size));
+        } else {
+            main = main.push(title("Accuracy over time"));
+            let start = self.history.0.len().saturating_sub(20);
+            for session in &self.history.0[start..] {
+                let files = session
+                    .files
+                    .iter()
+                    .filter_map(|file| Path::new(file).file_stem()?.to_str())
+                    .collect::<Vec<_>>();
+                let files = match files.is_empty() {
+                    true => String::from("default"),
+                    false => files.join(" + "),
+                };
+                main = main.push(bar(
+                    format!("{} {}", history::date(session.date), files),
+                    session.accuracy(),
+                    format!(
+                        "{} cards, {}:{:02}",
+                        session.answers.len(),
+                        session.duration / 60,
+                        session.duration % 60
+                    ),
+                ));
+            }
+
+            main = main.push(title("Most missed words"));
+            for (entry, mistakes, total) in self.history.most_missed(10) {
+                main = main.push(
+                    row![
+                        text(entry)
+                            .size(self.font_size)
+                            .width(Length::FillPortion(6)),
+                        text(format!("{} / {}", mistakes, total))
+                            .size(self.font_size)
+                            .color(style::TextColor::Red)
+                            .width(Length::FillPortion(3)),
+                    ]
+                    .spacing(self.spacing),
+                );
+            }
+
+            main = main.push(title("Accuracy by grammatical class"));
+            for (gram_class, accuracy, total) in self.history.accuracy_by_class() {
+                main = main.push(bar(
+                    gram_class.to_string(),
+                    accuracy,
+                    format!("{} answers", total),
+                ));
+            }
+        }
+
+        Element::from(column![header, scrollable(main).width(Length::Fill)])
+    }
+}
@@ -1,6 +1,8 @@
 use iced::{
-    advanced::widget::text, alignment, color, widget::button, Color, Length, Pixels, Renderer,
-    Theme,
+    advanced::widget::text,
+    alignment, color,
+    widget::{button, rich_text, span, text::Rich},
+    Color, Length, Pixels, Renderer, Theme,
 };
 
 pub fn header_button(theme: &Theme, _status: button::Status) -> button::Style {
@@ -21,6 +23,26 @@ pub fn style_text(
         .align_y(alignment::Vertical::Center)
 }
 
+/// Chars of `diff` in green when right and in red when wrong, see `grammar::fuzzy::diff`.
+pub fn diff_text<'a, Message: Clone + 'static>(
+    diff: &[(char, bool)],
+    font_size: Pixels,
+) -> Rich<'a, Message, Theme, Renderer> {
+    let spans = diff
+        .chunk_by(|a, b| a.1 == b.1)
+        .map(|chunk| {
+            span(chunk.iter().map(|(c, _)| c).collect::<String>()).color(match chunk[0].1 {
+                true => TextColor::Green,
+                false => TextColor::Red,
+            })
+        })
+        .collect::<Vec<_>>();
+    rich_text(spans)
+        .size(font_size)
+        .height(Length::Fixed(font_size.0 * 1.3 + 10.0))
+        .align_y(alignment::Vertical::Center)
+}
+
 #[derive(Clone)]
 pub enum TextColor {
     Red,